@@ -0,0 +1,61 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Also compiled in the tests of src/bundle.rs, to check it makes a valid Bundle
+#[path = "src/raw_bundle.rs"]
+mod raw_bundle;
+use raw_bundle::create_raw_bundle;
+
+// With the embedded-data feature, a bundle is put in the library. It is the file in the
+// POKEDLE_BUNDLE environment variable (made by create_bundle.py), otherwise it is made here from
+// the pokle dir in POKEDLE_DATA_DIR or poke_data, so the Python module is not needed to build it.
+fn main() {
+    println!("cargo:rerun-if-env-changed=POKEDLE_BUNDLE");
+    println!("cargo:rerun-if-env-changed=POKEDLE_DATA_DIR");
+    if env::var_os("CARGO_FEATURE_EMBEDDED_DATA").is_none() {
+        return;
+    }
+
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let bundle_file = match env::var("POKEDLE_BUNDLE") {
+        Ok(bundle_file) => {
+            let bundle_file = manifest_dir.join(bundle_file);
+            if !bundle_file.exists() {
+                panic!(
+                    "The bundle {} does not exist, it is needed by the embedded-data feature",
+                    bundle_file.display()
+                );
+            }
+            println!("cargo:rerun-if-changed={}", bundle_file.display());
+            bundle_file
+        }
+        Err(_) => {
+            let pokle_dir = get_pokle_dir(&manifest_dir);
+            println!("cargo:rerun-if-changed={}", pokle_dir.display());
+            let bundle_file =
+                PathBuf::from(env::var("OUT_DIR").unwrap()).join("pokedle_bundle.json");
+            fs::write(&bundle_file, create_raw_bundle(&pokle_dir).to_string()).unwrap();
+            bundle_file
+        }
+    };
+    println!(
+        "cargo:rustc-env=POKEDLE_EMBEDDED_BUNDLE={}",
+        bundle_file.display()
+    );
+}
+
+fn get_pokle_dir(manifest_dir: &Path) -> PathBuf {
+    if let Ok(pokle_dir) = env::var("POKEDLE_DATA_DIR") {
+        return manifest_dir.join(pokle_dir);
+    }
+    // An empty directory when the submodule is not checked out
+    let pokle_dir = manifest_dir.join("poke_data");
+    if !pokle_dir.join("generated_data").exists() {
+        panic!(
+            "There is no data to embed, set POKEDLE_BUNDLE or POKEDLE_DATA_DIR (small_test_data \
+             for the tests), or get the poke_data submodule"
+        );
+    }
+    pokle_dir
+}
@@ -0,0 +1,8 @@
+# Puts all the data in a single file, usage: python3 create_bundle.py [pokle_dir] [bundle_file]
+import sys
+
+import pokedle
+
+pokle_dir = sys.argv[1] if len(sys.argv) > 1 else "poke_data"
+bundle_file = sys.argv[2] if len(sys.argv) > 2 else "pokedle_bundle.json"
+pokedle.create_bundle(pokle_dir, bundle_file)
@@ -1,10 +1,34 @@
-from fastapi import FastAPI
+import os
+
+from fastapi import FastAPI, HTTPException
 from fastapi.staticfiles import StaticFiles
 from fastapi.middleware.cors import CORSMiddleware
 
 import pokedle
 
-p = pokedle.Pokedle("poke_data")
+# The seed must be the same on every instance to have the same daily pokemon everywhere
+seed = os.environ.get("POKEDLE_SEED")
+# Keeps the seed and the past daily pokemons across restarts
+state_file = os.environ.get("POKEDLE_STATE_FILE")
+# (timezone, hour) of the daily reset for each language, the default is 6h UTC
+reset_times = {
+	"fr": ("Europe/Paris", 0),
+	"de": ("Europe/Berlin", 0),
+}
+# Accept a pokemon which is not the same in every language or has an unknown type or color, only print a warning
+lenient = os.environ.get("POKEDLE_LENIENT") == "1"
+# All the data in a single file made with create_bundle.py, instead of the poke_data directory
+bundle_file = os.environ.get("POKEDLE_BUNDLE")
+config = pokedle.PokedleConfig(seed=int(seed) if seed is not None else None, reset_times=reset_times, state_file=state_file, reference_lang="fr", lenient=lenient)
+if bundle_file is not None:
+	p = pokedle.Pokedle.from_bundle(bundle_file, config)
+elif os.path.isdir("poke_data"):
+	p = pokedle.Pokedle("poke_data", config)
+else:
+	# The data embedded in the library, it needs the embedded-data feature
+	p = pokedle.Pokedle(config=config)
+for warning in p.get_data_warnings():
+	print(f"Warning: {warning}")
 
 app = FastAPI()
 app.add_middleware(
@@ -19,39 +43,72 @@ app.add_middleware(
 async def get_names(lang: str):
 	return p.get_names(lang)
 
+game_modes = {
+	"classic": pokedle.GameMode.Classic,
+	"base_stats": pokedle.GameMode.BaseStats,
+}
+
+def get_game_mode(mode: str):
+	if mode not in game_modes:
+		raise HTTPException(status_code=400, detail=f"Unknown game mode {mode}")
+	return game_modes[mode]
+
+# How the heights and the weights are shown, the imperial units are for the english-speaking players
+unit_systems = {
+	"metric": pokedle.UnitSystem.Metric,
+	"imperial": pokedle.UnitSystem.Imperial,
+}
+
+def get_unit_system(units: str):
+	if units not in unit_systems:
+		raise HTTPException(status_code=400, detail=f"Unknown unit system {units}")
+	return unit_systems[units]
+
 @app.post("/guess")
-async def guess(lang: str, pokemon_name: str):
-	comparison = p.guess(lang, pokemon_name)
-	pokemon = p.get_pokemon_by_name(lang, pokemon_name)
-	if len(pokemon.types) == 1:
-		pokemon_types_string = pokemon.types[0].to_string()
-	else:
-		pokemon_types_string = f"{pokemon.types[0].to_string()}, {pokemon.types[1].to_string()}"
+async def guess(lang: str, pokemon_name: str, puzzle_id: int | None = None, mode: str = "classic", units: str = "metric"):
+	try:
+		result = p.guess(lang, pokemon_name, puzzle_id, get_game_mode(mode), get_unit_system(units))
+	except pokedle.PuzzleExpiredError as e:
+		raise HTTPException(status_code=409, detail=str(e))
+	return guess_result_to_json(result)
 
+@app.post("/archive/guess")
+async def guess_for_puzzle(lang: str, pokemon_name: str, puzzle_id: int, mode: str = "classic", units: str = "metric"):
+	result = p.guess_for_puzzle(lang, puzzle_id, pokemon_name, get_game_mode(mode), get_unit_system(units))
+	return guess_result_to_json(result)
+
+@app.get("/archive/puzzles")
+async def get_past_puzzles(lang: str):
+	return [{"puzzle_id": puzzle_id, "date": date} for (puzzle_id, date) in p.get_past_puzzles(lang)]
+
+def guess_result_to_json(result):
 	return {
-		"success": comparison.success,
-		"height": {
-			"pokemon": pokemon.height.to_string(),
-			"comparison": comparison.height.to_string()
-		},
-		"weight": {
-			"pokemon": pokemon.weight.to_string(),
-			"comparison": comparison.weight.to_string()
-		},
-		"types": {
-			"pokemon": pokemon_types_string,
-			"comparison": comparison.types.to_string()
-		},
-		"color": {
-			"pokemon": pokemon.color.to_string(),
-			"comparison": comparison.color.to_string()
-		},
-		"generation": {
-			"pokemon": pokemon.generation.to_string(),
-			"comparison": comparison.generation.to_string()
-		},
+		"puzzle_id": result.puzzle_id,
+		"success": result.success,
+		# Every attribute of the game, in order, new attributes only need a comparator in the Rust code
+		"attributes": [attribute_result_to_json(attribute) for attribute in result.attributes],
 	}
 
+def attribute_result_to_json(attribute):
+	result = {
+		"name": attribute.attribute,
+		"pokemon": attribute.guessed_value,
+		"comparison": attribute.verdict.to_string() if attribute.verdict is not None else None
+	}
+	# Only for the numbers
+	if attribute.distance is not None:
+		result["difference"] = attribute.distance.difference
+		result["ratio"] = attribute.distance.ratio
+	# Only for the types
+	if attribute.slots is not None:
+		result["primary"] = attribute.slots.primary.to_string()
+		result["secondary"] = attribute.slots.secondary.to_string()
+	return result
+
+@app.get("/puzzle_id")
+async def get_puzzle_id(lang: str):
+	return p.get_puzzle_id(lang)
+
 @app.get("/previous_pokemon")
 async def get_previous_pokemon_to_guess_name(lang: str):
 	return p.get_previous_pokemon_to_guess_name(lang)
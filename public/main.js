@@ -1,4 +1,5 @@
 let pokemon_names = [];
+let puzzle_id = null;
 
 function onKeyPressed(event) {
 	const enterKeyCode = 13;
@@ -16,17 +17,24 @@ function is_local() {
 function add_cell(row, comparison, cell_text) {
 	let newCell = row.insertCell();
 
+	if (cell_text === null) {
+		cell_text = "?";
+	}
+
 	if (comparison === "equal") {
 		newCell.classList.add("equal_result");
 	} else if (comparison === "partially_equal") {
 		newCell.classList.add("partially_equal_result");
+	} else if (comparison === "close_higher" || comparison === "close_lower") {
+		newCell.classList.add("close_result");
 	} else {
 		newCell.classList.add("different_result");
-		if (comparison === "higher") {
-			cell_text += " ↑"
-		} else if (comparison === "lower") {
-			cell_text += " ↓"
-		}
+	}
+
+	if (comparison === "higher" || comparison === "close_higher") {
+		cell_text += " ↑"
+	} else if (comparison === "lower" || comparison === "close_lower") {
+		cell_text += " ↓"
 	}
 
 	let text_element = document.createTextNode(cell_text);
@@ -38,11 +46,13 @@ function add_row_in_result_table(results) {
 
 	let tbodyRef = document.getElementById('result_table').getElementsByTagName('tbody')[0];
 	let newRow = tbodyRef.insertRow();
-	add_cell(newRow, results.height.comparison, results.height.pokemon)
-	add_cell(newRow, results.weight.comparison, results.weight.pokemon)
-	add_cell(newRow, results.types.comparison, results.types.pokemon)
-	add_cell(newRow, results.color.comparison, results.color.pokemon)
-	add_cell(newRow, results.generation.comparison, results.generation.pokemon)
+	// The columns are the attributes in the header of the table
+	for (let header of document.querySelectorAll("#result_table th[data-attribute]")) {
+		let attribute = results.attributes.find((a) => a.name === header.dataset.attribute);
+		if (attribute !== undefined) {
+			add_cell(newRow, attribute.comparison, attribute.pokemon)
+		}
+	}
 
 	if (results.success) {
 		let a_surprise_for_later = document.getElementById("a_surprise_for_later");
@@ -73,10 +83,19 @@ async function try_guess_pokemon() {
 
 	url_guess.searchParams.append("pokemon_name", pokemon_name);
 	url_guess.searchParams.append("lang", "fr");
+	if (puzzle_id !== null) {
+		url_guess.searchParams.append("puzzle_id", puzzle_id);
+	}
 	let guess_response = await fetch(url_guess, options);
+	if (guess_response.status == 409) {
+		alert('The pokemon to guess changed, a new game starts.');
+		location.reload();
+		return;
+	}
 	let text_guess_response = await guess_response.text();
 	let guess_result = JSON.parse(text_guess_response);
 	console.log(guess_result);
+	puzzle_id = guess_result.puzzle_id;
 
 	add_row_in_result_table(guess_result);
 }
@@ -107,6 +126,17 @@ async function startup() {
 	let object_response = JSON.parse(response_text);
 	pokemon_names = object_response;
 	update_pokemon_list();
+
+	// The puzzle of the page, even the first guess is refused if the pokemon to guess changed since
+	let puzzle_id_url;
+	if (is_local()) {
+		puzzle_id_url = new URL("http://localhost:3412/puzzle_id");
+	} else {
+		puzzle_id_url = new URL("https://pokedle.baduit.eu/puzzle_id");
+	}
+	puzzle_id_url.searchParams.append('lang', 'fr');
+	let puzzle_id_response = await fetch(puzzle_id_url, options);
+	puzzle_id = JSON.parse(await puzzle_id_response.text());
 }
 
 startup()
\ No newline at end of file
@@ -0,0 +1,180 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::pokemon::{get_all_pokemons, get_names, read_json, ReadingError};
+use crate::{Lang, Pokemon, PokemonId};
+
+#[derive(Error, Debug)]
+pub enum BundleError {
+    #[error("{0}")]
+    ReadingError(#[from] ReadingError),
+    #[error("The language {0} has no pokemons")]
+    MissingPokemons(Lang),
+    #[error("The pokemon {1} has no id in {0}")]
+    MissingId(Lang, String),
+    #[error("The id {1} is used by several pokemons in {0}")]
+    DuplicateId(Lang, PokemonId),
+    #[error("Error while writing the bundle: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Error while serializing the bundle: {0}")]
+    SerdeError(#[from] serde_json::Error),
+}
+
+// All the generated_data directory in a single json file, so there is only one file to open and
+// to deploy
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Bundle {
+    pub languages: BTreeMap<Lang, BundleLang>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct BundleLang {
+    // Like in metadata.json
+    pub names: Vec<String>,
+    pub pokemons: Vec<Pokemon>,
+}
+
+impl Bundle {
+    pub fn from_dir(pokle_dir: &Path) -> Result<Bundle, BundleError> {
+        let mut all_pokemons = get_all_pokemons(pokle_dir.to_path_buf())?;
+        let mut languages = BTreeMap::new();
+        for (lang, names) in get_names(pokle_dir.to_path_buf())? {
+            let pokemons = match all_pokemons.remove(&lang) {
+                Some(pokemons) => pokemons,
+                None => return Err(BundleError::MissingPokemons(lang)),
+            };
+            languages.insert(lang, BundleLang { names, pokemons });
+        }
+        Ok(Bundle { languages })
+    }
+
+    // Sorted by id, like when the pokemons are read from the directory. The ids are not in the
+    // pokemon files, so they are the only thing checked here
+    pub fn into_pokemons(self) -> Result<BTreeMap<Lang, Vec<Pokemon>>, BundleError> {
+        let mut all_pokemons = BTreeMap::new();
+        for (lang, bundle_lang) in self.languages {
+            let mut pokemons = bundle_lang.pokemons;
+            // A missing id is read as 0
+            if let Some(pokemon) = pokemons.iter().find(|pokemon| pokemon.id == 0) {
+                return Err(BundleError::MissingId(lang, pokemon.name.clone()));
+            }
+            pokemons.sort_by_key(|pokemon| pokemon.id);
+            if let Some(pair) = pokemons.windows(2).find(|pair| pair[0].id == pair[1].id) {
+                return Err(BundleError::DuplicateId(lang, pair[0].id));
+            }
+            all_pokemons.insert(lang, pokemons);
+        }
+        Ok(all_pokemons)
+    }
+}
+
+pub fn read_bundle(filename: &Path) -> Result<Bundle, ReadingError> {
+    read_json(filename)
+}
+
+// The bundle put in the library at build time
+#[cfg(feature = "embedded-data")]
+pub fn get_embedded_bundle() -> Result<Bundle, serde_json::Error> {
+    serde_json::from_str(include_str!(env!("POKEDLE_EMBEDDED_BUNDLE")))
+}
+
+pub fn write_bundle(filename: &Path, bundle: &Bundle) -> Result<(), BundleError> {
+    let mut writer = BufWriter::new(File::create(filename)?);
+    serde_json::to_writer(&mut writer, bundle)?;
+    writer.flush()?;
+    Ok(())
+}
+
+// To make the bundle of a pokle dir before deploying it
+#[pyfunction]
+pub fn create_bundle(pokle_dir: PathBuf, bundle_file: PathBuf) -> PyResult<()> {
+    match Bundle::from_dir(&pokle_dir).and_then(|bundle| write_bundle(&bundle_file, &bundle)) {
+        Ok(()) => Ok(()),
+        Err(err) => Err(PyValueError::new_err(format!("{}", err))),
+    }
+}
+
+/*
+    Tests
+*/
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_and_read_bundle() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data");
+        let mut bundle_file = std::env::temp_dir();
+        bundle_file.push("pokedle_write_and_read_bundle.json");
+
+        let bundle = Bundle::from_dir(&d).unwrap();
+        assert_eq!(bundle.languages["fr"].names[0], "Bulbizarre");
+        write_bundle(&bundle_file, &bundle).unwrap();
+        let read = read_bundle(&bundle_file).unwrap();
+        assert_eq!(read, bundle);
+
+        // The same pokemons as in the directory, with their ids
+        assert_eq!(read.into_pokemons().unwrap(), get_all_pokemons(d).unwrap());
+
+        std::fs::remove_file(bundle_file).unwrap();
+    }
+
+    #[test]
+    fn raw_bundle_is_a_bundle() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data");
+
+        // What build.rs embeds must be readable as a Bundle
+        let raw_bundle = crate::raw_bundle::create_raw_bundle(&d);
+        let bundle: Bundle = serde_json::from_value(raw_bundle).unwrap();
+        let expected = Bundle::from_dir(&d).unwrap();
+        for (lang, bundle_lang) in &bundle.languages {
+            assert_eq!(bundle_lang.names, expected.languages[lang].names);
+        }
+        assert_eq!(
+            bundle.into_pokemons().unwrap(),
+            expected.into_pokemons().unwrap()
+        );
+    }
+
+    #[test]
+    fn bundle_with_invalid_ids() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data");
+
+        let mut bundle = Bundle::from_dir(&d).unwrap();
+        let pokemons = &mut bundle.languages.get_mut("fr").unwrap().pokemons;
+        pokemons[1].id = pokemons[0].id;
+        assert!(matches!(
+            bundle.into_pokemons(),
+            Err(BundleError::DuplicateId(lang, 1)) if lang == "fr"
+        ));
+
+        // Without an id in the json
+        let mut raw_bundle = crate::raw_bundle::create_raw_bundle(&d);
+        raw_bundle["languages"]["de"]["pokemons"][0]
+            .as_object_mut()
+            .unwrap()
+            .remove("id");
+        let bundle: Bundle = serde_json::from_value(raw_bundle).unwrap();
+        assert_eq!(
+            format!("{}", bundle.into_pokemons().unwrap_err()),
+            "The pokemon Bisasam has no id in de"
+        );
+    }
+
+    #[test]
+    fn read_invalid_bundle() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data/generated_data/fr/metadata.json");
+        read_bundle(&d).expect_err("This is not a bundle");
+    }
+}
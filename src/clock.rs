@@ -0,0 +1,70 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::prelude::*;
+use chrono::Duration;
+
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+// Time only moves when it is asked to, clones share the same time so a test can keep one to control
+// the clock given to the Pokedle
+#[derive(Clone)]
+pub struct FakeClock {
+    now: Arc<Mutex<DateTime<Utc>>>,
+}
+
+impl FakeClock {
+    pub fn new(now: DateTime<Utc>) -> FakeClock {
+        FakeClock {
+            now: Arc::new(Mutex::new(now)),
+        }
+    }
+
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap() = now;
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+}
+
+/*
+    Tests
+*/
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fake_clock_is_shared_between_clones() {
+        let start = Utc.with_ymd_and_hms(2024, 2, 28, 23, 0, 0).unwrap();
+        let clock = FakeClock::new(start);
+        let clock_copy = clock.clone();
+        assert_eq!(clock_copy.now(), start);
+
+        clock.advance(Duration::hours(2));
+        assert_eq!(
+            clock_copy.now(),
+            Utc.with_ymd_and_hms(2024, 2, 29, 1, 0, 0).unwrap()
+        );
+
+        clock_copy.set(start);
+        assert_eq!(clock.now(), start);
+    }
+}
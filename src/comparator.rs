@@ -0,0 +1,805 @@
+use pyo3::prelude::*;
+
+use crate::pokemon::compare_numbers;
+use crate::{
+    ClosenessThresholds, ColorComparison, ColorFamilies, ComparisonConfig, GameMode,
+    NumberComparison, NumberDistance, Pokemon, TypesComparison, UnitSystem,
+};
+
+#[pyclass]
+#[derive(Debug, PartialEq, Clone)]
+pub enum Verdict {
+    Equal,
+    PartiallyEqual,
+    Different,
+    // The value to guess is higher than the guess
+    Higher,
+    Lower,
+    // Same as Higher and Lower, but close to the value to guess
+    CloseHigher,
+    CloseLower,
+}
+
+#[pymethods]
+impl Verdict {
+    fn to_string(&self) -> String {
+        match self {
+            Self::Equal => String::from("equal"),
+            Self::PartiallyEqual => String::from("partially_equal"),
+            Self::Different => String::from("different"),
+            Self::Higher => String::from("higher"),
+            Self::Lower => String::from("lower"),
+            Self::CloseHigher => String::from("close_higher"),
+            Self::CloseLower => String::from("close_lower"),
+        }
+    }
+}
+
+#[pyclass]
+#[derive(Debug, PartialEq, Clone)]
+pub struct AttributeResult {
+    #[pyo3(get)]
+    pub attribute: String,
+    // None when the guess does not have this attribute, like in the older data files
+    #[pyo3(get)]
+    pub guessed_value: Option<String>,
+    // None when one of the pokemons does not have this attribute
+    #[pyo3(get)]
+    pub verdict: Option<Verdict>,
+    // Only for the numbers
+    #[pyo3(get)]
+    pub distance: Option<NumberDistance>,
+    // Only for the types
+    #[pyo3(get)]
+    pub slots: Option<TypesComparison>,
+}
+
+// Everything needed to add an attribute to a game
+pub trait AttributeComparator: Send + Sync {
+    fn get_name(&self) -> &str;
+    // The value shown to the player
+    fn get_value(&self, pokemon: &Pokemon) -> Option<String>;
+    fn compare(&self, guess: &Pokemon, pokemon_to_guess: &Pokemon) -> Option<Verdict>;
+
+    fn get_distance(
+        &self,
+        _guess: &Pokemon,
+        _pokemon_to_guess: &Pokemon,
+    ) -> Option<NumberDistance> {
+        None
+    }
+
+    fn get_slots(&self, _guess: &Pokemon, _pokemon_to_guess: &Pokemon) -> Option<TypesComparison> {
+        None
+    }
+}
+
+// The attributes compared in a game, the results are in the same order as the comparators
+pub struct GameDefinition {
+    comparators: Vec<Box<dyn AttributeComparator>>,
+}
+
+impl GameDefinition {
+    pub fn new(comparators: Vec<Box<dyn AttributeComparator>>) -> GameDefinition {
+        GameDefinition { comparators }
+    }
+
+    pub fn from_mode(
+        mode: &GameMode,
+        config: &ComparisonConfig,
+        units: UnitSystem,
+    ) -> GameDefinition {
+        let mut comparators = get_classic_comparators(config, units);
+        if *mode == GameMode::BaseStats {
+            comparators.extend(get_base_stats_comparators());
+        }
+        GameDefinition::new(comparators)
+    }
+
+    pub fn compare(&self, guess: &Pokemon, pokemon_to_guess: &Pokemon) -> Vec<AttributeResult> {
+        self.comparators
+            .iter()
+            .map(|comparator| AttributeResult {
+                attribute: String::from(comparator.get_name()),
+                guessed_value: comparator.get_value(guess),
+                verdict: comparator.compare(guess, pokemon_to_guess),
+                distance: comparator.get_distance(guess, pokemon_to_guess),
+                slots: comparator.get_slots(guess, pokemon_to_guess),
+            })
+            .collect()
+    }
+}
+
+pub enum CloseWhen {
+    Never,
+    // The difference is at most this ratio of the value to guess
+    Ratio(f64),
+    Difference(f64),
+}
+
+pub struct NumberComparator {
+    pub name: &'static str,
+    pub get_number: fn(&Pokemon) -> Option<f64>,
+    pub close_when: CloseWhen,
+}
+
+impl AttributeComparator for NumberComparator {
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_value(&self, pokemon: &Pokemon) -> Option<String> {
+        (self.get_number)(pokemon).map(|number| format!("{}", number))
+    }
+
+    fn compare(&self, guess: &Pokemon, pokemon_to_guess: &Pokemon) -> Option<Verdict> {
+        let guess = (self.get_number)(guess)?;
+        let value_to_guess = (self.get_number)(pokemon_to_guess)?;
+        Some(get_number_verdict(guess, value_to_guess, &self.close_when))
+    }
+
+    fn get_distance(&self, guess: &Pokemon, pokemon_to_guess: &Pokemon) -> Option<NumberDistance> {
+        let guess = (self.get_number)(guess)?;
+        let value_to_guess = (self.get_number)(pokemon_to_guess)?;
+        Some(NumberDistance::new(guess, value_to_guess))
+    }
+}
+
+// A number with a unit, the value shown depends on the unit system of the player.
+// It is compared in tenths (decimetres and hectograms), so it is exact.
+pub struct MeasureComparator {
+    pub name: &'static str,
+    pub get_tenths: fn(&Pokemon) -> u32,
+    pub format: fn(&Pokemon, UnitSystem) -> String,
+    pub ratio: f64,
+    pub units: UnitSystem,
+}
+
+impl AttributeComparator for MeasureComparator {
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_value(&self, pokemon: &Pokemon) -> Option<String> {
+        Some((self.format)(pokemon, self.units))
+    }
+
+    fn compare(&self, guess: &Pokemon, pokemon_to_guess: &Pokemon) -> Option<Verdict> {
+        Some(get_number_verdict(
+            (self.get_tenths)(guess).into(),
+            (self.get_tenths)(pokemon_to_guess).into(),
+            &CloseWhen::Ratio(self.ratio),
+        ))
+    }
+
+    fn get_distance(&self, guess: &Pokemon, pokemon_to_guess: &Pokemon) -> Option<NumberDistance> {
+        Some(NumberDistance::from_tenths(
+            (self.get_tenths)(guess),
+            (self.get_tenths)(pokemon_to_guess),
+        ))
+    }
+}
+
+fn get_number_verdict(guess: f64, value_to_guess: f64, close_when: &CloseWhen) -> Verdict {
+    let difference = (guess - value_to_guess).abs();
+    let is_close = match close_when {
+        CloseWhen::Never => false,
+        CloseWhen::Ratio(ratio) => difference <= value_to_guess.abs() * ratio,
+        CloseWhen::Difference(max_difference) => difference <= *max_difference,
+    };
+    match (compare_numbers(&guess, &value_to_guess), is_close) {
+        (NumberComparison::Equal, _) => Verdict::Equal,
+        (NumberComparison::Higher, false) => Verdict::Higher,
+        (NumberComparison::Higher, true) => Verdict::CloseHigher,
+        (NumberComparison::Lower, false) => Verdict::Lower,
+        (NumberComparison::Lower, true) => Verdict::CloseLower,
+    }
+}
+
+pub struct EqualityComparator {
+    pub name: &'static str,
+    pub get_text: fn(&Pokemon) -> Option<String>,
+}
+
+impl AttributeComparator for EqualityComparator {
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_value(&self, pokemon: &Pokemon) -> Option<String> {
+        (self.get_text)(pokemon)
+    }
+
+    fn compare(&self, guess: &Pokemon, pokemon_to_guess: &Pokemon) -> Option<Verdict> {
+        let guess = (self.get_text)(guess)?;
+        let value_to_guess = (self.get_text)(pokemon_to_guess)?;
+        if guess == value_to_guess {
+            Some(Verdict::Equal)
+        } else {
+            Some(Verdict::Different)
+        }
+    }
+}
+
+pub struct TypesComparator;
+
+impl AttributeComparator for TypesComparator {
+    fn get_name(&self) -> &str {
+        "types"
+    }
+
+    fn get_value(&self, pokemon: &Pokemon) -> Option<String> {
+        let types: Vec<String> = pokemon.types.iter().map(|t| t.name.clone()).collect();
+        Some(types.join(", "))
+    }
+
+    fn compare(&self, guess: &Pokemon, pokemon_to_guess: &Pokemon) -> Option<Verdict> {
+        let comparison = TypesComparison::new(&guess.types, &pokemon_to_guess.types);
+        if comparison.is_equal() {
+            Some(Verdict::Equal)
+        } else if comparison.has_common_type() {
+            Some(Verdict::PartiallyEqual)
+        } else {
+            Some(Verdict::Different)
+        }
+    }
+
+    fn get_slots(&self, guess: &Pokemon, pokemon_to_guess: &Pokemon) -> Option<TypesComparison> {
+        Some(TypesComparison::new(&guess.types, &pokemon_to_guess.types))
+    }
+}
+
+pub struct ColorComparator {
+    pub families: ColorFamilies,
+}
+
+impl AttributeComparator for ColorComparator {
+    fn get_name(&self) -> &str {
+        "color"
+    }
+
+    fn get_value(&self, pokemon: &Pokemon) -> Option<String> {
+        Some(pokemon.color.name.clone())
+    }
+
+    fn compare(&self, guess: &Pokemon, pokemon_to_guess: &Pokemon) -> Option<Verdict> {
+        // A similar color gets the same partial credit as a common type
+        match self.families.compare(&guess.color, &pokemon_to_guess.color) {
+            ColorComparison::Equal => Some(Verdict::Equal),
+            ColorComparison::Similar => Some(Verdict::PartiallyEqual),
+            ColorComparison::Different => Some(Verdict::Different),
+        }
+    }
+}
+
+fn get_classic_comparators(
+    config: &ComparisonConfig,
+    units: UnitSystem,
+) -> Vec<Box<dyn AttributeComparator>> {
+    let thresholds: &ClosenessThresholds = &config.closeness_thresholds;
+    vec![
+        Box::new(MeasureComparator {
+            name: "height",
+            get_tenths: |pokemon| pokemon.height.0,
+            format: |pokemon, units| pokemon.height.format(units),
+            ratio: thresholds.height_ratio,
+            units,
+        }),
+        Box::new(MeasureComparator {
+            name: "weight",
+            get_tenths: |pokemon| pokemon.weight.0,
+            format: |pokemon, units| pokemon.weight.format(units),
+            ratio: thresholds.weight_ratio,
+            units,
+        }),
+        Box::new(TypesComparator),
+        Box::new(ColorComparator {
+            families: config.color_families.clone(),
+        }),
+        Box::new(NumberComparator {
+            name: "generation",
+            get_number: |pokemon| Some(pokemon.generation.0.into()),
+            close_when: CloseWhen::Difference(thresholds.generation.into()),
+        }),
+        Box::new(NumberComparator {
+            name: "evolution_stage",
+            get_number: |pokemon| pokemon.evolution_stage.as_ref().map(|stage| stage.0.into()),
+            close_when: CloseWhen::Never,
+        }),
+        Box::new(EqualityComparator {
+            name: "habitat",
+            get_text: |pokemon| pokemon.habitat.as_ref().map(|habitat| habitat.0.clone()),
+        }),
+        Box::new(EqualityComparator {
+            name: "shape",
+            get_text: |pokemon| pokemon.shape.as_ref().map(|shape| shape.0.clone()),
+        }),
+        Box::new(EqualityComparator {
+            name: "status",
+            get_text: |pokemon| pokemon.status.as_ref().map(|status| status.to_string()),
+        }),
+    ]
+}
+
+fn get_base_stats_comparators() -> Vec<Box<dyn AttributeComparator>> {
+    let base_stat = |name, get_number| -> Box<dyn AttributeComparator> {
+        Box::new(NumberComparator {
+            name,
+            get_number,
+            close_when: CloseWhen::Never,
+        })
+    };
+    vec![
+        base_stat("hp", |pokemon| {
+            pokemon.base_stats.as_ref().map(|stats| stats.hp.into())
+        }),
+        base_stat("attack", |pokemon| {
+            pokemon.base_stats.as_ref().map(|stats| stats.attack.into())
+        }),
+        base_stat("defense", |pokemon| {
+            pokemon
+                .base_stats
+                .as_ref()
+                .map(|stats| stats.defense.into())
+        }),
+        base_stat("special_attack", |pokemon| {
+            pokemon
+                .base_stats
+                .as_ref()
+                .map(|stats| stats.special_attack.into())
+        }),
+        base_stat("special_defense", |pokemon| {
+            pokemon
+                .base_stats
+                .as_ref()
+                .map(|stats| stats.special_defense.into())
+        }),
+        base_stat("speed", |pokemon| {
+            pokemon.base_stats.as_ref().map(|stats| stats.speed.into())
+        }),
+        base_stat("total", |pokemon| {
+            pokemon
+                .base_stats
+                .as_ref()
+                .map(|stats| stats.get_total().into())
+        }),
+    ]
+}
+
+/*
+    Tests
+*/
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        BaseStats, Color, EvolutionStage, Generation, Habitat, Height, Shape, Status, Type,
+        TypeSlotComparison, Weight,
+    };
+
+    fn get_pichu() -> Pokemon {
+        Pokemon {
+            id: 172,
+            name: String::from("Pichu"),
+            height: Height(3),
+            weight: Weight(20),
+            types: vec![Type::from_name("Électrik")],
+            color: Color::from_name("Jaune"),
+            generation: Generation(2),
+            evolution_stage: None,
+            habitat: None,
+            shape: None,
+            status: Some(Status::Baby),
+            base_stats: Some(BaseStats {
+                hp: 20,
+                attack: 40,
+                defense: 15,
+                special_attack: 35,
+                special_defense: 35,
+                speed: 60,
+            }),
+        }
+    }
+
+    fn get_pikachu() -> Pokemon {
+        Pokemon {
+            id: 25,
+            name: String::from("Pikachu"),
+            height: Height(4),
+            weight: Weight(60),
+            types: vec![Type::from_name("Électrik")],
+            color: Color::from_name("Jaune"),
+            generation: Generation(1),
+            evolution_stage: None,
+            habitat: None,
+            shape: None,
+            status: Some(Status::Normal),
+            base_stats: Some(BaseStats {
+                hp: 35,
+                attack: 55,
+                defense: 40,
+                special_attack: 50,
+                special_defense: 35,
+                speed: 90,
+            }),
+        }
+    }
+
+    fn get_chrysacier() -> Pokemon {
+        Pokemon {
+            id: 11,
+            name: String::from("Chrysacier"),
+            height: Height(7),
+            weight: Weight(99),
+            types: vec![Type::from_name("Insecte")],
+            color: Color::from_name("Vert"),
+            generation: Generation(1),
+            evolution_stage: None,
+            habitat: None,
+            shape: None,
+            status: None,
+            base_stats: None,
+        }
+    }
+
+    fn get_verdicts(results: &[AttributeResult]) -> Vec<(&str, Option<Verdict>)> {
+        results
+            .iter()
+            .map(|result| (result.attribute.as_str(), result.verdict.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn classic_game() {
+        let game =
+            GameDefinition::from_mode(&GameMode::Classic, &Default::default(), UnitSystem::Metric);
+        let results = game.compare(&get_pichu(), &get_pikachu());
+        assert_eq!(
+            get_verdicts(&results),
+            vec![
+                ("height", Some(Verdict::Higher)),
+                ("weight", Some(Verdict::Higher)),
+                ("types", Some(Verdict::Equal)),
+                ("color", Some(Verdict::Equal)),
+                ("generation", Some(Verdict::CloseLower)),
+                ("evolution_stage", None),
+                ("habitat", None),
+                ("shape", None),
+                ("status", Some(Verdict::Different)),
+            ]
+        );
+        assert_eq!(results[0].guessed_value, Some(String::from("0.3 m")));
+        assert_eq!(results[2].guessed_value, Some(String::from("Électrik")));
+        assert_eq!(results[5].guessed_value, None);
+        assert_eq!(results[8].guessed_value, Some(String::from("baby")));
+
+        assert_eq!(
+            results[0].distance,
+            Some(NumberDistance {
+                difference: 0.1,
+                ratio: Some(1.3333333333333333),
+            })
+        );
+        assert_eq!(
+            results[4].distance,
+            Some(NumberDistance {
+                difference: -1.0,
+                ratio: Some(0.5),
+            })
+        );
+        assert_eq!(results[3].distance, None);
+        assert_eq!(
+            results[2].slots,
+            Some(TypesComparison {
+                primary: TypeSlotComparison::Equal,
+                secondary: TypeSlotComparison::Equal,
+            })
+        );
+        assert_eq!(results[3].slots, None);
+    }
+
+    #[test]
+    fn base_stats_game() {
+        let game = GameDefinition::from_mode(
+            &GameMode::BaseStats,
+            &Default::default(),
+            UnitSystem::Metric,
+        );
+        let results = game.compare(&get_pichu(), &get_pikachu());
+        assert_eq!(
+            get_verdicts(&results[9..]),
+            vec![
+                ("hp", Some(Verdict::Higher)),
+                ("attack", Some(Verdict::Higher)),
+                ("defense", Some(Verdict::Higher)),
+                ("special_attack", Some(Verdict::Higher)),
+                ("special_defense", Some(Verdict::Equal)),
+                ("speed", Some(Verdict::Higher)),
+                ("total", Some(Verdict::Higher)),
+            ]
+        );
+        assert_eq!(results[15].guessed_value, Some(String::from("205")));
+
+        // Only in the base stats mode
+        let classic_game =
+            GameDefinition::from_mode(&GameMode::Classic, &Default::default(), UnitSystem::Metric);
+        assert_eq!(classic_game.compare(&get_pichu(), &get_pikachu()).len(), 9);
+
+        // The older data files do not have the base stats
+        let old_pikachu = Pokemon {
+            base_stats: None,
+            ..get_pikachu()
+        };
+        let results = game.compare(&get_pichu(), &old_pikachu);
+        assert!(results[9..].iter().all(|result| result.verdict.is_none()));
+    }
+
+    #[test]
+    fn imperial_units() {
+        let game = GameDefinition::from_mode(
+            &GameMode::Classic,
+            &Default::default(),
+            UnitSystem::Imperial,
+        );
+        let results = game.compare(&get_pichu(), &get_pikachu());
+        assert_eq!(results[0].guessed_value, Some(String::from("1'00\"")));
+        assert_eq!(results[1].guessed_value, Some(String::from("4.4 lbs")));
+        // The units only change what is shown
+        assert_eq!(results[0].verdict, Some(Verdict::Higher));
+    }
+
+    #[test]
+    fn custom_game() {
+        // Only one place to touch to add an attribute
+        let game = GameDefinition::new(vec![Box::new(EqualityComparator {
+            name: "first_letter",
+            get_text: |pokemon| pokemon.name.chars().next().map(String::from),
+        })]);
+        assert_eq!(
+            game.compare(&get_pichu(), &get_pikachu()),
+            vec![AttributeResult {
+                attribute: String::from("first_letter"),
+                guessed_value: Some(String::from("P")),
+                verdict: Some(Verdict::Equal),
+                distance: None,
+                slots: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn same_pokemon() {
+        let game =
+            GameDefinition::from_mode(&GameMode::Classic, &Default::default(), UnitSystem::Metric);
+        let results = game.compare(&get_chrysacier(), &get_chrysacier());
+        assert!(results[..5]
+            .iter()
+            .all(|result| result.verdict == Some(Verdict::Equal)));
+        assert_eq!(
+            results[0].distance,
+            Some(NumberDistance {
+                difference: 0.0,
+                ratio: Some(1.0),
+            })
+        );
+    }
+
+    #[test]
+    fn partially_equal_types_and_lower_numbers() {
+        let my_creature = Pokemon {
+            id: 0,
+            name: String::from("my_creature"),
+            height: Height(8),
+            weight: Weight(100),
+            types: vec![Type::from_name("Insecte"), Type::from_name("Feu")],
+            color: Color::from_name("Rouge"),
+            generation: Generation(2),
+            ..get_chrysacier()
+        };
+        let game =
+            GameDefinition::from_mode(&GameMode::Classic, &Default::default(), UnitSystem::Metric);
+        let results = game.compare(&my_creature, &get_chrysacier());
+        assert_eq!(
+            get_verdicts(&results[..5]),
+            vec![
+                ("height", Some(Verdict::Lower)),
+                ("weight", Some(Verdict::CloseLower)),
+                ("types", Some(Verdict::PartiallyEqual)),
+                ("color", Some(Verdict::Different)),
+                ("generation", Some(Verdict::CloseLower)),
+            ]
+        );
+        assert_eq!(
+            results[1].distance,
+            Some(NumberDistance {
+                difference: -0.1,
+                ratio: Some(0.99),
+            })
+        );
+        assert_eq!(
+            results[2].slots,
+            Some(TypesComparison {
+                primary: TypeSlotComparison::Equal,
+                secondary: TypeSlotComparison::Unexpected,
+            })
+        );
+
+        // Reversed, nothing in common
+        let my_creature = Pokemon {
+            types: vec![Type::from_name("Acier"), Type::from_name("Feu")],
+            ..my_creature
+        };
+        let results = game.compare(&get_chrysacier(), &my_creature);
+        assert_eq!(
+            get_verdicts(&results[..5]),
+            vec![
+                ("height", Some(Verdict::Higher)),
+                ("weight", Some(Verdict::CloseHigher)),
+                ("types", Some(Verdict::Different)),
+                ("color", Some(Verdict::Different)),
+                ("generation", Some(Verdict::CloseHigher)),
+            ]
+        );
+        assert_eq!(
+            results[2].slots,
+            Some(TypesComparison {
+                primary: TypeSlotComparison::Different,
+                secondary: TypeSlotComparison::Missing,
+            })
+        );
+    }
+
+    #[test]
+    fn closeness_thresholds() {
+        let my_creature = Pokemon {
+            id: 0,
+            name: String::from("my_creature"),
+            height: Height(8),
+            weight: Weight(200),
+            generation: Generation(4),
+            ..get_chrysacier()
+        };
+        let get_number_verdicts = |config: &ComparisonConfig, guess: &Pokemon, target: &Pokemon| {
+            let game = GameDefinition::from_mode(&GameMode::Classic, config, UnitSystem::Metric);
+            let results = game.compare(guess, target);
+            vec![
+                results[0].verdict.clone(),
+                results[1].verdict.clone(),
+                results[4].verdict.clone(),
+            ]
+        };
+
+        let config = Default::default();
+        assert_eq!(
+            get_number_verdicts(&config, &my_creature, &get_chrysacier()),
+            vec![Some(Verdict::Lower); 3]
+        );
+
+        let config = ComparisonConfig {
+            closeness_thresholds: ClosenessThresholds::new(0.2, 1.5, 3),
+            ..Default::default()
+        };
+        assert_eq!(
+            get_number_verdicts(&config, &my_creature, &get_chrysacier()),
+            vec![Some(Verdict::CloseLower); 3]
+        );
+        assert_eq!(
+            get_number_verdicts(&config, &get_chrysacier(), &my_creature),
+            vec![Some(Verdict::CloseHigher); 3]
+        );
+
+        // Nothing is close without a threshold
+        let config = ComparisonConfig {
+            closeness_thresholds: ClosenessThresholds::new(0.0, 0.0, 0),
+            ..Default::default()
+        };
+        assert_eq!(
+            get_number_verdicts(&config, &my_creature, &get_chrysacier()),
+            vec![Some(Verdict::Lower); 3]
+        );
+    }
+
+    #[test]
+    fn optional_attributes() {
+        let pichu = Pokemon {
+            evolution_stage: Some(EvolutionStage(1)),
+            habitat: Some(Habitat(String::from("Forêt"))),
+            shape: Some(Shape(String::from("Quadrupède"))),
+            ..get_pichu()
+        };
+        let raichu = Pokemon {
+            id: 26,
+            name: String::from("Raichu"),
+            height: Height(8),
+            weight: Weight(300),
+            generation: Generation(1),
+            evolution_stage: Some(EvolutionStage(3)),
+            habitat: Some(Habitat(String::from("Forêt"))),
+            shape: Some(Shape(String::from("Bipède"))),
+            status: Some(Status::Normal),
+            ..get_pichu()
+        };
+        let game =
+            GameDefinition::from_mode(&GameMode::Classic, &Default::default(), UnitSystem::Metric);
+
+        assert_eq!(
+            get_verdicts(&game.compare(&pichu, &raichu)[5..]),
+            vec![
+                ("evolution_stage", Some(Verdict::Higher)),
+                ("habitat", Some(Verdict::Equal)),
+                ("shape", Some(Verdict::Different)),
+                ("status", Some(Verdict::Different)),
+            ]
+        );
+        assert_eq!(
+            get_verdicts(&game.compare(&raichu, &raichu)[5..]),
+            vec![
+                ("evolution_stage", Some(Verdict::Equal)),
+                ("habitat", Some(Verdict::Equal)),
+                ("shape", Some(Verdict::Equal)),
+                ("status", Some(Verdict::Equal)),
+            ]
+        );
+
+        // Nothing to compare if one of the pokemons does not have the attribute
+        let old_raichu = Pokemon {
+            evolution_stage: None,
+            habitat: None,
+            shape: None,
+            status: None,
+            ..raichu
+        };
+        assert!(game.compare(&pichu, &old_raichu)[5..]
+            .iter()
+            .all(|result| result.verdict.is_none()));
+    }
+
+    #[test]
+    fn compare_by_id() {
+        let bulbizarre = Pokemon {
+            id: 1,
+            name: String::from("Bulbizarre"),
+            types: vec![Type::from_name("Plante"), Type::from_name("Poison")],
+            ..get_chrysacier()
+        };
+        let game =
+            GameDefinition::from_mode(&GameMode::Classic, &Default::default(), UnitSystem::Metric);
+
+        // A typo in the name does not matter when there is the id
+        let mut guess = Pokemon {
+            id: 0,
+            name: String::from("my_creature"),
+            types: vec![Type::new("grass", "Plnate"), Type::from_name("Poison")],
+            color: Color::new("green", "Vret"),
+            ..bulbizarre.clone()
+        };
+        let results = game.compare(&guess, &bulbizarre);
+        assert_eq!(results[2].verdict, Some(Verdict::Equal));
+        assert_eq!(results[3].verdict, Some(Verdict::Equal));
+
+        // The names of another language have the same ids
+        guess.types = vec![Type::from_name("Pflanze"), Type::from_name("Gift")];
+        guess.color = Color::from_name("Grün");
+        let results = game.compare(&guess, &bulbizarre);
+        assert_eq!(results[2].verdict, Some(Verdict::Equal));
+        assert_eq!(results[3].verdict, Some(Verdict::Equal));
+    }
+
+    #[test]
+    fn similar_colors() {
+        let guess = Pokemon {
+            color: Color::from_name("Rose"),
+            ..get_chrysacier()
+        };
+        let target = Pokemon {
+            color: Color::from_name("Rouge"),
+            ..get_chrysacier()
+        };
+        let game =
+            GameDefinition::from_mode(&GameMode::Classic, &Default::default(), UnitSystem::Metric);
+        assert_eq!(
+            game.compare(&guess, &target)[3].verdict,
+            Some(Verdict::PartiallyEqual)
+        );
+    }
+}
@@ -0,0 +1,526 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::prelude::*;
+use chrono::Duration;
+#[cfg(test)]
+use chrono::LocalResult;
+use chrono_tz::Tz;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{PokedleError, PokemonId};
+
+// The daily pokemon is shared by all the languages, so it is stored by id and each
+// PokemonHandler gives the pokemon in its own language.
+// It only depends on the seed and the date of the puzzle, so every process with the same seed
+// has the same daily pokemon, even after a restart.
+//
+// The days are grouped in cycles as long as the number of pokemons, each cycle is a shuffled
+// permutation of all the pokemons so there is no repeat inside a cycle. Between 2 cycles, the
+// pokemons of the end of a cycle can't be at the beginning of the next one, so 2 occurrences of
+// the same pokemon are always separated by more than no_repeat_window days. The cycles are computed
+// from the current list of pokemons, so this does not hold across a change of the list: the first
+// days after the change may repeat a pokemon of the days before.
+//
+// The pokemons already played are kept in the history, it has priority over the computed ones, so
+// the past puzzles stay the same even if the seed or the pokemons change.
+//
+// The overrides are the pokemons chosen by hand for special dates, they have priority over the
+// computed pokemons and ignore the no repeat window. They can't change a date already played, the
+// history wins over them.
+//
+// The archive starts at the launch date when it is configured, otherwise at the first date of the
+// history because the days before were never played.
+pub struct DailySchedule {
+    pokemon_ids: Vec<PokemonId>,
+    seed: u64,
+    launch_date: NaiveDate,
+    is_launch_date_configured: bool,
+    no_repeat_window: usize,
+    history: BTreeMap<NaiveDate, PokemonId>,
+    overrides: BTreeMap<NaiveDate, PokemonId>,
+}
+
+impl DailySchedule {
+    pub fn new(
+        pokemon_ids: Vec<PokemonId>,
+        seed: u64,
+        launch_date: Option<NaiveDate>,
+        no_repeat_window: Option<usize>,
+        history: BTreeMap<NaiveDate, PokemonId>,
+    ) -> Result<DailySchedule, PokedleError> {
+        // Above a third of the pokemons, it is not always possible to reorder the beginning of a cycle
+        let max_no_repeat_window = pokemon_ids.len() / 3;
+        let no_repeat_window = no_repeat_window.unwrap_or(max_no_repeat_window);
+        if no_repeat_window > max_no_repeat_window {
+            return Err(PokedleError::NoRepeatWindowTooBig(
+                no_repeat_window,
+                max_no_repeat_window,
+            ));
+        }
+
+        Ok(DailySchedule {
+            pokemon_ids,
+            seed,
+            launch_date: launch_date.unwrap_or_else(get_default_launch_date),
+            is_launch_date_configured: launch_date.is_some(),
+            no_repeat_window,
+            history,
+            overrides: BTreeMap::new(),
+        })
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn get_history(&self) -> &BTreeMap<NaiveDate, PokemonId> {
+        &self.history
+    }
+
+    // Returns true if the pokemon of this date was not in the history yet
+    pub fn add_to_history(&mut self, date: NaiveDate) -> bool {
+        if self.history.contains_key(&date) {
+            return false;
+        }
+        let pokemon_id = self.get_pokemon_id_for_date(date);
+        self.history.insert(date, pokemon_id);
+        true
+    }
+
+    pub fn get_overrides(&self) -> &BTreeMap<NaiveDate, PokemonId> {
+        &self.overrides
+    }
+
+    // Only after the last date played, the puzzles already played never change
+    pub fn set_override(
+        &mut self,
+        date: NaiveDate,
+        pokemon_id: PokemonId,
+    ) -> Result<(), PokedleError> {
+        if let Some(last_played_date) = self.history.keys().next_back() {
+            if date <= *last_played_date {
+                return Err(PokedleError::PuzzleAlreadyPlayed(date));
+            }
+        }
+        self.check_pokemon_id(pokemon_id)?;
+        self.overrides.insert(date, pokemon_id);
+        Ok(())
+    }
+
+    // The overrides of the schedule file, the ones of the dates already played are ignored
+    pub fn set_overrides(
+        &mut self,
+        overrides: BTreeMap<NaiveDate, PokemonId>,
+    ) -> Result<(), PokedleError> {
+        for pokemon_id in overrides.values() {
+            self.check_pokemon_id(*pokemon_id)?;
+        }
+        self.overrides = overrides;
+        Ok(())
+    }
+
+    fn check_pokemon_id(&self, pokemon_id: PokemonId) -> Result<(), PokedleError> {
+        if !self.pokemon_ids.contains(&pokemon_id) {
+            return Err(PokedleError::PokemonIdDoesNotExist(pokemon_id));
+        }
+        Ok(())
+    }
+
+    // Number of days since the launch, so it increases every day
+    pub fn get_puzzle_id(&self, date: NaiveDate) -> PuzzleId {
+        (date - self.launch_date).num_days()
+    }
+
+    pub fn get_puzzle_date(&self, puzzle_id: PuzzleId) -> NaiveDate {
+        self.launch_date + Duration::days(puzzle_id)
+    }
+
+    // The oldest puzzle which can be played in archive mode
+    pub fn get_first_puzzle_id(&self) -> PuzzleId {
+        let first_date = match self.history.keys().next() {
+            Some(first_date) if !self.is_launch_date_configured => *first_date,
+            _ => self.launch_date,
+        };
+        self.get_puzzle_id(first_date).max(0)
+    }
+
+    pub fn get_pokemon_id_for_date(&self, date: NaiveDate) -> PokemonId {
+        if let Some(pokemon_id) = self.history.get(&date) {
+            return *pokemon_id;
+        }
+        if let Some(pokemon_id) = self.overrides.get(&date) {
+            return *pokemon_id;
+        }
+
+        let number_of_pokemons = self.pokemon_ids.len() as i64;
+        let day = (date - self.launch_date).num_days();
+        let cycle = day.div_euclid(number_of_pokemons);
+        let day_in_cycle = day.rem_euclid(number_of_pokemons) as usize;
+        self.get_cycle(cycle)[day_in_cycle]
+    }
+
+    fn get_cycle(&self, cycle: i64) -> Vec<PokemonId> {
+        let mut pokemons = self.get_shuffled_pokemons(cycle);
+
+        // Only the beginning of a cycle and the middle are reordered, never the end, so the end of
+        // the previous cycle does not depend on the one before it
+        let previous_pokemons = self.get_shuffled_pokemons(cycle - 1);
+        let previous_end: BTreeSet<PokemonId> = previous_pokemons
+            [previous_pokemons.len() - self.no_repeat_window..]
+            .iter()
+            .copied()
+            .collect();
+        let middle_end = pokemons.len() - self.no_repeat_window;
+        let mut candidate = self.no_repeat_window;
+        for i in 0..self.no_repeat_window {
+            if !previous_end.contains(&pokemons[i]) {
+                continue;
+            }
+            // There is always a candidate because the window is at most a third of the pokemons
+            while previous_end.contains(&pokemons[candidate]) {
+                candidate += 1;
+            }
+            debug_assert!(candidate < middle_end);
+            pokemons.swap(i, candidate);
+            candidate += 1;
+        }
+        pokemons
+    }
+
+    fn get_shuffled_pokemons(&self, cycle: i64) -> Vec<PokemonId> {
+        // ChaCha is used because its output is guaranteed to be the same on every platform and version
+        let mut rng_seed = <ChaCha8Rng as SeedableRng>::Seed::default();
+        rng_seed[..8].copy_from_slice(&self.seed.to_le_bytes());
+        rng_seed[8..16].copy_from_slice(&cycle.to_le_bytes());
+        let mut rng = ChaCha8Rng::from_seed(rng_seed);
+
+        let mut pokemons = self.pokemon_ids.clone();
+        pokemons.shuffle(&mut rng);
+        pokemons
+    }
+}
+
+pub type PuzzleId = i64;
+
+pub fn get_default_launch_date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2023, 10, 1).unwrap()
+}
+
+// When the daily pokemon changes, in local time
+#[derive(Debug, PartialEq, Clone)]
+pub struct ResetTime {
+    pub timezone: Tz,
+    pub hour: u32,
+}
+
+impl ResetTime {
+    pub fn new(timezone: &str, hour: u32) -> Result<ResetTime, PokedleError> {
+        let timezone: Tz = match timezone.parse() {
+            Ok(timezone) => timezone,
+            Err(_) => return Err(PokedleError::InvalidTimezone(String::from(timezone))),
+        };
+        if hour >= 24 {
+            return Err(PokedleError::InvalidResetHour(hour));
+        }
+        Ok(ResetTime { timezone, hour })
+    }
+
+    // The date of the puzzle being played at this moment
+    pub fn get_puzzle_date(&self, datetime: DateTime<Utc>) -> NaiveDate {
+        // Comparing the wall clock time works even when the reset hour is skipped or repeated by a DST change
+        let local_datetime = datetime.with_timezone(&self.timezone);
+        if local_datetime.hour() < self.hour {
+            local_datetime.date_naive() - Duration::days(1)
+        } else {
+            local_datetime.date_naive()
+        }
+    }
+}
+
+// Only the tests need to know when a puzzle starts, the puzzle date is enough for the rest
+#[cfg(test)]
+impl ResetTime {
+    // The moment the puzzle of this date starts
+    pub fn get_reset_datetime(&self, puzzle_date: NaiveDate) -> DateTime<Utc> {
+        let reset = puzzle_date.and_hms_opt(self.hour, 0, 0).unwrap();
+        match self.timezone.from_local_datetime(&reset) {
+            LocalResult::Single(datetime) => datetime.with_timezone(&Utc),
+            LocalResult::Ambiguous(earliest, _) => earliest.with_timezone(&Utc),
+            // The reset hour is skipped by a DST change, the puzzle starts when the clocks jump,
+            // which is always less than a day later
+            LocalResult::None => (1..=24 * 60)
+                .find_map(|minutes| {
+                    self.timezone
+                        .from_local_datetime(&(reset + Duration::minutes(minutes)))
+                        .earliest()
+                })
+                .expect("The clocks jump by less than a day")
+                .with_timezone(&Utc),
+        }
+    }
+}
+
+impl Default for ResetTime {
+    fn default() -> ResetTime {
+        ResetTime {
+            timezone: Tz::UTC,
+            hour: 6,
+        }
+    }
+}
+
+/*
+    Tests
+*/
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn daily_pokemon_is_deterministic() {
+        let pokemon_ids: Vec<PokemonId> = (1..1000).collect();
+        let schedule = new_schedule(pokemon_ids.clone(), 42, None);
+        let first_day = NaiveDate::from_ymd_opt(2023, 10, 1).unwrap();
+
+        let mut different_pokemon_found = false;
+        for day in 0..100 {
+            let date = first_day + Duration::days(day);
+            let id = schedule.get_pokemon_id_for_date(date);
+            assert!(pokemon_ids.contains(&id));
+            // Same seed and same date, so same pokemon, even with another instance
+            assert_eq!(
+                id,
+                new_schedule(pokemon_ids.clone(), 42, None).get_pokemon_id_for_date(date)
+            );
+            if id != schedule.get_pokemon_id_for_date(first_day) {
+                different_pokemon_found = true;
+            }
+        }
+        // The date is really used
+        assert!(different_pokemon_found);
+
+        // And the seed too
+        assert!((0..100).any(|seed| {
+            new_schedule(pokemon_ids.clone(), seed, None).get_pokemon_id_for_date(first_day)
+                != schedule.get_pokemon_id_for_date(first_day)
+        }));
+    }
+
+    #[test]
+    fn daily_pokemon_cycle_through_all_pokemons() {
+        let pokemon_ids: Vec<PokemonId> = (1..=100).collect();
+        let schedule = new_schedule(pokemon_ids.clone(), 42, Some(0));
+
+        // Every cycle, even before the launch date, contains all the pokemons
+        for cycle in -2..3 {
+            let first_day = get_default_launch_date() + Duration::days(cycle * 100);
+            let mut cycle_pokemons: Vec<PokemonId> = (0..100)
+                .map(|day| schedule.get_pokemon_id_for_date(first_day + Duration::days(day)))
+                .collect();
+            cycle_pokemons.sort();
+            assert_eq!(cycle_pokemons, pokemon_ids);
+        }
+    }
+
+    #[test]
+    fn daily_pokemon_no_repeat_window() {
+        let pokemon_ids: Vec<PokemonId> = (1..=100).collect();
+        for seed in 0..5 {
+            let schedule = new_schedule(pokemon_ids.clone(), seed, Some(30));
+            let pokemons: Vec<PokemonId> = (0..1000)
+                .map(|day| {
+                    schedule
+                        .get_pokemon_id_for_date(get_default_launch_date() + Duration::days(day))
+                })
+                .collect();
+            for days in pokemons.windows(31) {
+                let mut days = days.to_vec();
+                days.sort();
+                days.dedup();
+                assert_eq!(days.len(), 31);
+            }
+        }
+    }
+
+    #[test]
+    fn daily_pokemon_no_repeat_window_too_big() {
+        // At most a third of the pokemons
+        let pokemon_ids: Vec<PokemonId> = (1..=5).collect();
+        let schedule = DailySchedule::new(pokemon_ids, 42, None, Some(2), BTreeMap::new());
+        assert!(matches!(
+            schedule,
+            Err(PokedleError::NoRepeatWindowTooBig(2, 1))
+        ));
+    }
+
+    #[test]
+    fn daily_pokemon_history() {
+        let mut schedule = new_schedule((1..=100).collect(), 42, None);
+        let date = NaiveDate::from_ymd_opt(2023, 10, 14).unwrap();
+        let pokemon_id = schedule.get_pokemon_id_for_date(date);
+
+        assert!(schedule.add_to_history(date));
+        assert!(!schedule.add_to_history(date));
+        assert_eq!(
+            schedule.get_history(),
+            &BTreeMap::from([(date, pokemon_id)])
+        );
+
+        // The history wins, even if the schedule would give another pokemon
+        let history = BTreeMap::from([(date, 101)]);
+        let schedule = DailySchedule::new((1..=100).collect(), 42, None, None, history).unwrap();
+        assert_eq!(schedule.get_pokemon_id_for_date(date), 101);
+    }
+
+    #[test]
+    fn daily_pokemon_override() {
+        let mut schedule = new_schedule((1..=100).collect(), 42, None);
+        let played_date = NaiveDate::from_ymd_opt(2023, 10, 14).unwrap();
+        let next_date = NaiveDate::from_ymd_opt(2023, 10, 31).unwrap();
+        schedule.add_to_history(played_date);
+
+        // The override of a date not played yet does not touch the history
+        let history = schedule.get_history().clone();
+        schedule.set_override(next_date, 94).unwrap();
+        assert_eq!(schedule.get_pokemon_id_for_date(next_date), 94);
+        assert_eq!(schedule.get_history(), &history);
+
+        // A date already played can't change, nor a date before it
+        let other_pokemon_id = if history[&played_date] == 1 { 2 } else { 1 };
+        assert!(matches!(
+            schedule.set_override(played_date, other_pokemon_id),
+            Err(PokedleError::PuzzleAlreadyPlayed(date)) if date == played_date
+        ));
+        schedule
+            .set_override(played_date.pred_opt().unwrap(), other_pokemon_id)
+            .expect_err("This date is over");
+        assert_eq!(schedule.get_overrides(), &BTreeMap::from([(next_date, 94)]));
+
+        // The overrides of the schedule file are not checked against the history, but it wins
+        schedule
+            .set_overrides(BTreeMap::from([(played_date, other_pokemon_id)]))
+            .unwrap();
+        assert_eq!(
+            schedule.get_pokemon_id_for_date(played_date),
+            history[&played_date]
+        );
+        schedule
+            .set_overrides(BTreeMap::from([(next_date, 94)]))
+            .unwrap();
+
+        // Only the pokemons of the schedule can be chosen
+        schedule
+            .set_override(next_date, 101)
+            .expect_err("This pokemon does not exist");
+        assert_eq!(schedule.get_pokemon_id_for_date(next_date), 94);
+    }
+
+    #[test]
+    fn puzzle_id() {
+        let schedule = new_schedule((1..=100).collect(), 42, None);
+        let launch_date = get_default_launch_date();
+        assert_eq!(schedule.get_puzzle_id(launch_date), 0);
+        assert_eq!(schedule.get_puzzle_id(launch_date + Duration::days(1)), 1);
+        assert_eq!(
+            schedule.get_puzzle_id(launch_date + Duration::days(365)),
+            365
+        );
+    }
+
+    #[test]
+    fn first_puzzle_id() {
+        let launch_date = get_default_launch_date();
+        let history = BTreeMap::from([(launch_date + Duration::days(10), 1)]);
+        let schedule =
+            DailySchedule::new((1..=100).collect(), 42, None, None, history.clone()).unwrap();
+        assert_eq!(schedule.get_first_puzzle_id(), 10);
+
+        // A configured launch date wins over the history
+        let schedule =
+            DailySchedule::new((1..=100).collect(), 42, Some(launch_date), None, history).unwrap();
+        assert_eq!(schedule.get_first_puzzle_id(), 0);
+
+        let schedule = new_schedule((1..=100).collect(), 42, None);
+        assert_eq!(schedule.get_first_puzzle_id(), 0);
+    }
+
+    #[test]
+    fn reset_time_creation() {
+        assert_eq!(
+            ResetTime::new("Europe/Paris", 0).unwrap(),
+            ResetTime {
+                timezone: Tz::Europe__Paris,
+                hour: 0
+            }
+        );
+        ResetTime::new("Europe/Pariss", 0).expect_err("This timezone does not exist");
+        ResetTime::new("Europe/Paris", 24).expect_err("This hour does not exist");
+    }
+
+    #[test]
+    fn reset_time_puzzle_date() {
+        let reset_time = ResetTime::new("Europe/Paris", 0).unwrap();
+        // 23h59 in Paris
+        assert_eq!(
+            reset_time.get_puzzle_date(Utc.with_ymd_and_hms(2023, 10, 14, 21, 59, 59).unwrap()),
+            NaiveDate::from_ymd_opt(2023, 10, 14).unwrap()
+        );
+        // Midnight in Paris
+        assert_eq!(
+            reset_time.get_puzzle_date(Utc.with_ymd_and_hms(2023, 10, 14, 22, 0, 0).unwrap()),
+            NaiveDate::from_ymd_opt(2023, 10, 15).unwrap()
+        );
+
+        let reset_time = ResetTime::default();
+        assert_eq!(
+            reset_time.get_puzzle_date(Utc.with_ymd_and_hms(2023, 10, 14, 5, 59, 59).unwrap()),
+            NaiveDate::from_ymd_opt(2023, 10, 13).unwrap()
+        );
+        assert_eq!(
+            reset_time.get_puzzle_date(Utc.with_ymd_and_hms(2023, 10, 14, 6, 0, 0).unwrap()),
+            NaiveDate::from_ymd_opt(2023, 10, 14).unwrap()
+        );
+    }
+
+    #[test]
+    fn reset_time_reset_datetime() {
+        let reset_time = ResetTime::new("Europe/Paris", 0).unwrap();
+        assert_eq!(
+            reset_time.get_reset_datetime(NaiveDate::from_ymd_opt(2023, 10, 15).unwrap()),
+            Utc.with_ymd_and_hms(2023, 10, 14, 22, 0, 0).unwrap()
+        );
+
+        // 2h does not exist the 31 of march 2024 in Paris, the clocks go from 2h to 3h
+        let reset_time = ResetTime::new("Europe/Paris", 2).unwrap();
+        let reset_datetime =
+            reset_time.get_reset_datetime(NaiveDate::from_ymd_opt(2024, 3, 31).unwrap());
+        assert_eq!(
+            reset_datetime,
+            Utc.with_ymd_and_hms(2024, 3, 31, 1, 0, 0).unwrap()
+        );
+        assert_eq!(
+            reset_time.get_puzzle_date(reset_datetime),
+            NaiveDate::from_ymd_opt(2024, 3, 31).unwrap()
+        );
+        assert_eq!(
+            reset_time.get_puzzle_date(reset_datetime - Duration::seconds(1)),
+            NaiveDate::from_ymd_opt(2024, 3, 30).unwrap()
+        );
+
+        // 2h happens twice the 27 of october 2024 in Paris, the first one is used
+        assert_eq!(
+            reset_time.get_reset_datetime(NaiveDate::from_ymd_opt(2024, 10, 27).unwrap()),
+            Utc.with_ymd_and_hms(2024, 10, 27, 0, 0, 0).unwrap()
+        );
+    }
+
+    fn new_schedule(
+        pokemon_ids: Vec<PokemonId>,
+        seed: u64,
+        no_repeat_window: Option<usize>,
+    ) -> DailySchedule {
+        DailySchedule::new(pokemon_ids, seed, None, no_repeat_window, BTreeMap::new()).unwrap()
+    }
+}
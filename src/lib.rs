@@ -1,173 +1,484 @@
 use pokemon::get_all_pokemons;
-use rand::distributions::Uniform;
-use rand::Rng;
-use std::iter::zip;
-use std::{collections::BTreeMap, path::PathBuf};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use chrono::prelude::*;
 use thiserror::Error;
 
+use pyo3::create_exception;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
+mod bundle;
+#[cfg(feature = "embedded-data")]
+pub use bundle::get_embedded_bundle;
+pub use bundle::{create_bundle, read_bundle, write_bundle, Bundle, BundleError, BundleLang};
+// Made for build.rs
+#[cfg(test)]
+mod raw_bundle;
+
+mod clock;
+pub use clock::{Clock, FakeClock, SystemClock};
+
+mod comparator;
+pub use comparator::{
+    AttributeComparator, AttributeResult, CloseWhen, EqualityComparator, GameDefinition,
+    MeasureComparator, NumberComparator, TypesComparator, Verdict,
+};
+
+mod daily;
+use daily::DailySchedule;
+pub use daily::{PuzzleId, ResetTime};
+
+mod state;
+pub use state::StateError;
+use state::{read_overrides, read_state, write_overrides, write_state, DailyState};
+
+mod names;
+
+mod validation;
+pub use validation::{validate_data, DataIssue};
+
 mod pokemon;
 pub use pokemon::{
-    compare_pokemons, get_names, Color, ColorComparison, Generation, Height, Lang,
-    NumberComparison, Pokemon, PokemonComparison, ReadingError, Type, TypesComparison, Weight,
+    check_consistency, check_names, get_names, BaseStats, ClosenessThresholds, Color,
+    ColorComparison, ColorFamilies, ComparisonConfig, EvolutionStage, GameMode, Generation,
+    Habitat, Height, InconsistentPokemon, InvalidMeasure, Lang, NumberComparison, NumberDistance,
+    Pokemon, PokemonId, ReadingError, Shape, Status, Type, TypeSlotComparison, TypesComparison,
+    UnitSystem, UnknownName, Weight,
 };
 
 #[pymodule]
 #[pyo3(name = "pokedle")]
-fn pokedle_module(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+fn pokedle_module(py: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_class::<Pokedle>()?;
+    m.add_class::<PokedleConfig>()?;
+    m.add_class::<DataIssue>()?;
+    m.add_function(wrap_pyfunction!(validate_data, m)?)?;
+    m.add_function(wrap_pyfunction!(create_bundle, m)?)?;
     m.add_class::<Pokemon>()?;
     m.add_class::<Height>()?;
     m.add_class::<Weight>()?;
+    m.add_class::<UnitSystem>()?;
     m.add_class::<Type>()?;
     m.add_class::<Color>()?;
     m.add_class::<Generation>()?;
-    m.add_class::<PokemonComparison>()?;
     m.add_class::<TypesComparison>()?;
+    m.add_class::<TypeSlotComparison>()?;
     m.add_class::<NumberComparison>()?;
+    m.add_class::<NumberDistance>()?;
     m.add_class::<ColorComparison>()?;
+    m.add_class::<EvolutionStage>()?;
+    m.add_class::<Habitat>()?;
+    m.add_class::<Shape>()?;
+    m.add_class::<Status>()?;
+    m.add_class::<BaseStats>()?;
+    m.add_class::<GameMode>()?;
+    m.add_class::<Verdict>()?;
+    m.add_class::<AttributeResult>()?;
+    m.add_class::<ClosenessThresholds>()?;
+    m.add_class::<GuessResult>()?;
+    m.add("PuzzleExpiredError", py.get_type::<PuzzleExpiredError>())?;
     Ok(())
 }
 
+create_exception!(pokedle, PuzzleExpiredError, PyValueError);
+
 #[derive(Error, Debug)]
 pub enum PokedleError {
     #[error("The language {0} does not exist")]
     LangDoesNotExist(String),
     #[error("The pokemon {0} does not exist")]
     PokemonDoesNotExist(String),
+    #[error("The pokemon with the id {0} does not exist")]
+    PokemonIdDoesNotExist(PokemonId),
+    #[error("The timezone {0} does not exist")]
+    InvalidTimezone(String),
+    #[error("The reset hour {0} is not between 0 and 23")]
+    InvalidResetHour(u32),
+    #[error("The puzzle {0} is not available")]
+    PuzzleNotAvailable(PuzzleId),
+    #[error(
+        "The no repeat window {0} is too big, it can't be more than {1} (a third of the pokemons)"
+    )]
+    NoRepeatWindowTooBig(usize, usize),
+    #[error("The puzzle of {0} is already played, it can't be changed")]
+    PuzzleAlreadyPlayed(NaiveDate),
+    #[error("The puzzle {expected} is over, the current puzzle is {current}")]
+    PuzzleExpired {
+        expected: PuzzleId,
+        current: PuzzleId,
+    },
 }
 
 struct PokemonHandler {
-    pokemon_names: Vec<String>,
-    pokemons: Vec<Pokemon>,
-    daily_pokemon_index: usize,
-    last_pokemon_update: DateTime<Utc>,
-    previous_daily_pokemon_index: Option<usize>,
-    // Todo: add an id to know if the pokemon changed while the player was playing
+    // Keyed by id, so the names and the pokemons are always in the same order
+    pokemons: BTreeMap<PokemonId, Pokemon>,
+    reset_time: ResetTime,
+    clock: Arc<dyn Clock>,
+    puzzle_date: NaiveDate,
+    daily_pokemon_id: PokemonId,
+    previous_daily_pokemon_id: Option<PokemonId>,
 }
 
 impl PokemonHandler {
-    pub fn new(pokemon_names: Vec<String>, pokemons: Vec<Pokemon>) -> PokemonHandler {
-        let number_of_pokemons = pokemons.len();
-
-        // We want to set the generation time in the night
-        let current_datetime = Utc::now();
-        let first_generation = Utc
-            .with_ymd_and_hms(
-                current_datetime.year(),
-                current_datetime.month(),
-                current_datetime.day(),
-                6,
-                0,
-                0,
-            )
-            .unwrap();
+    pub fn new(
+        pokemons: Vec<Pokemon>,
+        schedule: &mut DailySchedule,
+        reset_time: ResetTime,
+        clock: Arc<dyn Clock>,
+    ) -> PokemonHandler {
+        let puzzle_date = reset_time.get_puzzle_date(clock.now());
+        schedule.add_to_history(puzzle_date);
 
         PokemonHandler {
-            pokemon_names,
-            pokemons,
-            daily_pokemon_index: PokemonHandler::get_random_pokemon_index(number_of_pokemons),
-            last_pokemon_update: first_generation,
-            previous_daily_pokemon_index: None,
+            pokemons: pokemons
+                .into_iter()
+                .map(|pokemon| (pokemon.id, pokemon))
+                .collect(),
+            reset_time,
+            clock,
+            puzzle_date,
+            daily_pokemon_id: schedule.get_pokemon_id_for_date(puzzle_date),
+            previous_daily_pokemon_id: PokemonHandler::get_previous_pokemon_id(
+                schedule,
+                puzzle_date,
+            ),
         }
     }
 
-    fn get_random_pokemon_index(number_of_pokemons: usize) -> usize {
-        let mut rng = rand::thread_rng();
-        let pokemon_distribution = Uniform::new(0, number_of_pokemons);
-        rng.sample(pokemon_distribution)
+    // The previous pokemon is computed and not remembered, so it is right even after a restart or
+    // after several days without any guess
+    fn get_previous_pokemon_id(
+        schedule: &DailySchedule,
+        puzzle_date: NaiveDate,
+    ) -> Option<PokemonId> {
+        puzzle_date
+            .pred_opt()
+            .map(|date| schedule.get_pokemon_id_for_date(date))
+    }
+
+    // In the order of the ids
+    pub fn get_names(&self) -> Vec<String> {
+        self.pokemons
+            .values()
+            .map(|pokemon| pokemon.name.clone())
+            .collect()
     }
 
     pub fn get_pokemon_by_name(&self, name: &str) -> Result<&Pokemon, PokedleError> {
-        match self.pokemons.iter().find(|p| p.name == name) {
+        match self.pokemons.values().find(|p| p.name == name) {
             Some(pokemon) => Ok(pokemon),
             None => Err(PokedleError::PokemonDoesNotExist(String::from(name))),
         }
     }
 
-    pub fn get_daily_pokemon(&self) -> &Pokemon {
-        // Safe to unwrap, because the index is generated from the size of the vec and the vec has a constant size
-        self.pokemons.get(self.daily_pokemon_index).unwrap()
+    pub fn get_pokemon_by_id(&self, id: PokemonId) -> Result<&Pokemon, PokedleError> {
+        match self.pokemons.get(&id) {
+            Some(pokemon) => Ok(pokemon),
+            None => Err(PokedleError::PokemonIdDoesNotExist(id)),
+        }
+    }
+
+    pub fn get_daily_pokemon(&self) -> Result<&Pokemon, PokedleError> {
+        self.get_pokemon_by_id(self.daily_pokemon_id)
     }
 
-    pub fn update_daily_pokemon_if_needed(&mut self) {
-        if self.is_update_needed() {
-            self.previous_daily_pokemon_index = Some(self.daily_pokemon_index);
-            self.daily_pokemon_index =
-                PokemonHandler::get_random_pokemon_index(self.pokemons.len());
-            self.last_pokemon_update = Utc::now();
+    pub fn compare(
+        &self,
+        pokemon_name: &str,
+        pokemon_to_guess: &Pokemon,
+        game: &GameDefinition,
+    ) -> Result<(bool, Vec<AttributeResult>), PokedleError> {
+        let input_pokemon = self.get_pokemon_by_name(pokemon_name)?;
+        Ok((
+            input_pokemon.id == pokemon_to_guess.id,
+            game.compare(input_pokemon, pokemon_to_guess),
+        ))
+    }
+
+    // Returns true if the history of the schedule changed
+    pub fn update_daily_pokemon_if_needed(&mut self, schedule: &mut DailySchedule) -> bool {
+        // The puzzle date comes from the clock, so the pokemon changes exactly at the reset time
+        // even if nobody played for hours
+        let puzzle_date = self.reset_time.get_puzzle_date(self.clock.now());
+        if puzzle_date == self.puzzle_date {
+            return false;
         }
+
+        let history_changed = schedule.add_to_history(puzzle_date);
+        self.puzzle_date = puzzle_date;
+        self.daily_pokemon_id = schedule.get_pokemon_id_for_date(puzzle_date);
+        self.previous_daily_pokemon_id =
+            PokemonHandler::get_previous_pokemon_id(schedule, puzzle_date);
+        history_changed
     }
+}
+
+// Given to the constructors of Pokedle, the same for the pokle dir and the bundle
+#[pyclass]
+#[derive(Default, Clone)]
+pub struct PokedleConfig {
+    // Must be the same for all the processes which must have the same daily pokemon, random if not set
+    pub seed: Option<u64>,
+    // The languages without a reset time use the default one
+    pub reset_times: BTreeMap<Lang, ResetTime>,
+    // Minimum number of days before a pokemon can be the daily pokemon again, at most a third of the
+    // pokemons and the biggest possible if not set
+    pub no_repeat_window: Option<usize>,
+    // The date of the puzzle 0 and of the oldest puzzle of the archive, if not set the archive starts
+    // at the first date played
+    pub launch_date: Option<NaiveDate>,
+    // Where the seed and the history of the daily pokemons are saved, nothing is saved if not set
+    pub state_file: Option<PathBuf>,
+    // The pokemons chosen by hand for some dates, schedule.json next to the state file if not set,
+    // there are no overrides without both
+    pub schedule_file: Option<PathBuf>,
+    pub comparison: ComparisonConfig,
+    // The language the other languages are checked against, the first one if not set
+    pub reference_lang: Option<Lang>,
+    // With it, a pokemon which is not the same in every language or has an unknown type or color is
+    // only a warning
+    pub lenient: bool,
+}
 
-    fn is_update_needed(&self) -> bool {
-        let diff_time = Utc::now() - self.last_pokemon_update;
-        diff_time.num_days() >= 1
+#[pymethods]
+impl PokedleConfig {
+    // All the arguments are optional keyword arguments in Python
+    #[new]
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (*, seed=None, reset_times=None, no_repeat_window=None, launch_date=None, state_file=None, schedule_file=None, closeness_thresholds=None, color_families=None, reference_lang=None, lenient=false))]
+    pub fn new(
+        seed: Option<u64>,
+        reset_times: Option<BTreeMap<Lang, (String, u32)>>,
+        no_repeat_window: Option<usize>,
+        launch_date: Option<NaiveDate>,
+        state_file: Option<PathBuf>,
+        schedule_file: Option<PathBuf>,
+        closeness_thresholds: Option<ClosenessThresholds>,
+        color_families: Option<Vec<Vec<String>>>,
+        reference_lang: Option<Lang>,
+        lenient: bool,
+    ) -> PyResult<PokedleConfig> {
+        Ok(PokedleConfig {
+            seed,
+            reset_times: get_reset_times(reset_times.unwrap_or_default())?,
+            no_repeat_window,
+            launch_date,
+            state_file,
+            schedule_file,
+            comparison: ComparisonConfig {
+                closeness_thresholds: closeness_thresholds.unwrap_or_default(),
+                color_families: color_families.map(ColorFamilies::new).unwrap_or_default(),
+            },
+            reference_lang,
+            lenient,
+        })
     }
 }
 
+// The reset times are given as (timezone, hour) in Python
+fn get_reset_times(
+    reset_times: BTreeMap<Lang, (String, u32)>,
+) -> PyResult<BTreeMap<Lang, ResetTime>> {
+    let mut parsed_reset_times = BTreeMap::new();
+    for (lang, (timezone, hour)) in reset_times {
+        match ResetTime::new(&timezone, hour) {
+            Ok(reset_time) => parsed_reset_times.insert(lang, reset_time),
+            Err(err) => return Err(PyValueError::new_err(format!("{}", err))),
+        };
+    }
+    Ok(parsed_reset_times)
+}
+
+#[pyclass]
+#[derive(Debug, PartialEq, Clone)]
+pub struct GuessResult {
+    // Allows the player to know if the pokemon changed while playing
+    #[pyo3(get)]
+    pub puzzle_id: PuzzleId,
+    #[pyo3(get)]
+    pub success: bool,
+    // In the order of the game definition
+    #[pyo3(get)]
+    pub attributes: Vec<AttributeResult>,
+}
+
 #[pyclass]
 pub struct Pokedle {
     handlers: BTreeMap<Lang, PokemonHandler>,
+    schedule: DailySchedule,
+    state_file: Option<PathBuf>,
+    schedule_file: Option<PathBuf>,
+    comparison_config: ComparisonConfig,
+    data_warnings: Vec<String>,
 }
 
 #[pymethods]
 impl Pokedle {
+    // Without a pokle dir, the data is the one embedded in the library, only with the
+    // embedded-data feature
     #[new]
-    pub fn new(pokle_dir: &str) -> PyResult<Self> {
-        let names = match get_names(PathBuf::from(pokle_dir)) {
-            Ok(names) => names,
-            Err(err) => return Err(PyValueError::new_err(format!("{}", err))),
-        };
-        let pokemons = match get_all_pokemons(PathBuf::from(pokle_dir)) {
-            Ok(pokemons) => pokemons,
-            Err(err) => return Err(PyValueError::new_err(format!("{}", err))),
-        };
+    #[pyo3(signature = (pokle_dir=None, config=None))]
+    pub fn new(pokle_dir: Option<&str>, config: Option<PokedleConfig>) -> PyResult<Self> {
+        let config = config.unwrap_or_default();
+        match pokle_dir {
+            Some(pokle_dir) => Pokedle::with_clock(pokle_dir, config, Arc::new(SystemClock)),
+            None => Pokedle::embedded_with_clock(config, Arc::new(SystemClock)),
+        }
+    }
 
-        let mut pokedle = Pokedle {
-            handlers: BTreeMap::new(),
-        };
+    // Same as the constructor, but all the data is in a single file made by create_bundle
+    #[staticmethod]
+    #[pyo3(signature = (bundle_file, config=None))]
+    pub fn from_bundle(bundle_file: PathBuf, config: Option<PokedleConfig>) -> PyResult<Self> {
+        Pokedle::from_bundle_with_clock(
+            &bundle_file,
+            config.unwrap_or_default(),
+            Arc::new(SystemClock),
+        )
+    }
 
-        for ((name_lang, names), (pokemon_lang, pokemons)) in zip(names, pokemons) {
-            if name_lang != pokemon_lang {
-                return Err(PyValueError::new_err("Incoherent data"));
+    // If the player gives the puzzle they are playing and the daily pokemon changed since, the guess is refused
+    #[pyo3(signature = (lang, pokemon_name, puzzle_id=None, mode=GameMode::Classic, units=UnitSystem::Metric))]
+    pub fn guess(
+        &mut self,
+        lang: &str,
+        pokemon_name: &str,
+        puzzle_id: Option<PuzzleId>,
+        mode: GameMode,
+        units: UnitSystem,
+    ) -> PyResult<GuessResult> {
+        self.update_daily_pokemon_if_needed(lang)?;
+        let handler = self.get_handler(lang)?;
+
+        let current_puzzle_id = self.schedule.get_puzzle_id(handler.puzzle_date);
+        match puzzle_id {
+            Some(expected) if expected != current_puzzle_id => {
+                let err = PokedleError::PuzzleExpired {
+                    expected,
+                    current: current_puzzle_id,
+                };
+                return Err(PuzzleExpiredError::new_err(format!("{}", err)));
             }
+            _ => (),
+        }
 
-            pokedle
-                .handlers
-                .insert(name_lang, PokemonHandler::new(names, pokemons));
+        let result = handler.get_daily_pokemon().and_then(|daily_pokemon| {
+            self.get_guess_result(
+                handler,
+                pokemon_name,
+                daily_pokemon,
+                current_puzzle_id,
+                &mode,
+                units,
+            )
+        });
+        match result {
+            Ok(result) => Ok(result),
+            Err(err) => Err(PyValueError::new_err(format!("{}", err))),
         }
-        Ok(pokedle)
     }
 
-    pub fn guess(&mut self, lang: &str, pokemon_name: &str) -> PyResult<PokemonComparison> {
-        let handler = match self.handlers.get_mut(lang) {
-            Some(handler) => handler,
-            None => {
-                return Err(PyValueError::new_err(format!(
-                    "Langage {} does not exist.",
-                    lang
-                )))
-            }
-        };
-        handler.update_daily_pokemon_if_needed();
+    // Archive mode, to play the puzzle of a previous day
+    #[pyo3(signature = (lang, date, pokemon_name, mode=GameMode::Classic, units=UnitSystem::Metric))]
+    pub fn guess_for_date(
+        &mut self,
+        lang: &str,
+        date: NaiveDate,
+        pokemon_name: &str,
+        mode: GameMode,
+        units: UnitSystem,
+    ) -> PyResult<GuessResult> {
+        let puzzle_id = self.schedule.get_puzzle_id(date);
+        self.guess_for_puzzle(lang, puzzle_id, pokemon_name, mode, units)
+    }
 
-        let daily_pokemon = handler.get_daily_pokemon();
+    #[pyo3(signature = (lang, puzzle_id, pokemon_name, mode=GameMode::Classic, units=UnitSystem::Metric))]
+    pub fn guess_for_puzzle(
+        &mut self,
+        lang: &str,
+        puzzle_id: PuzzleId,
+        pokemon_name: &str,
+        mode: GameMode,
+        units: UnitSystem,
+    ) -> PyResult<GuessResult> {
+        self.update_daily_pokemon_if_needed(lang)?;
+        let handler = self.get_handler(lang)?;
 
-        let input_pokemon = match handler.get_pokemon_by_name(pokemon_name) {
-            Ok(input_pokemon) => input_pokemon,
-            Err(err) => return Err(PyValueError::new_err(format!("{}", err))),
+        // The puzzles of the next days must stay a secret
+        let current_puzzle_id = self.schedule.get_puzzle_id(handler.puzzle_date);
+        if puzzle_id < self.schedule.get_first_puzzle_id() || puzzle_id > current_puzzle_id {
+            let err = PokedleError::PuzzleNotAvailable(puzzle_id);
+            return Err(PyValueError::new_err(format!("{}", err)));
+        }
+
+        let puzzle_date = self.schedule.get_puzzle_date(puzzle_id);
+        let pokemon_to_guess_id = self.schedule.get_pokemon_id_for_date(puzzle_date);
+        let result = handler
+            .get_pokemon_by_id(pokemon_to_guess_id)
+            .and_then(|pokemon_to_guess| {
+                self.get_guess_result(
+                    handler,
+                    pokemon_name,
+                    pokemon_to_guess,
+                    puzzle_id,
+                    &mode,
+                    units,
+                )
+            });
+        match result {
+            Ok(result) => Ok(result),
+            Err(err) => Err(PyValueError::new_err(format!("{}", err))),
+        }
+    }
+
+    // The puzzles which can be played in archive mode, from the most recent to the oldest
+    pub fn get_past_puzzles(&mut self, lang: &str) -> PyResult<Vec<(PuzzleId, NaiveDate)>> {
+        self.update_daily_pokemon_if_needed(lang)?;
+        let handler = self.get_handler(lang)?;
+
+        let current_puzzle_id = self.schedule.get_puzzle_id(handler.puzzle_date);
+        Ok((self.schedule.get_first_puzzle_id()..current_puzzle_id)
+            .rev()
+            .map(|puzzle_id| (puzzle_id, self.schedule.get_puzzle_date(puzzle_id)))
+            .collect())
+    }
+
+    pub fn get_puzzle_id(&mut self, lang: &str) -> PyResult<PuzzleId> {
+        self.update_daily_pokemon_if_needed(lang)?;
+        let handler = self.get_handler(lang)?;
+        Ok(self.schedule.get_puzzle_id(handler.puzzle_date))
+    }
+
+    // Chooses the daily pokemon of a date, in every language, it is saved in the schedule file.
+    // Only the dates after the current puzzles can be chosen.
+    pub fn set_daily_override(&mut self, date: NaiveDate, pokemon_id: PokemonId) -> PyResult<()> {
+        if let Err(err) = self.schedule.set_override(date, pokemon_id) {
+            return Err(PyValueError::new_err(format!("{}", err)));
+        }
+        let schedule_file = match &self.schedule_file {
+            Some(schedule_file) => schedule_file,
+            None => return Err(PyValueError::new_err(
+                "There is nowhere to save the override, set the schedule file or the state file",
+            )),
         };
-        let comparison = compare_pokemons(input_pokemon, daily_pokemon);
-        Ok(comparison)
+        if let Err(err) = write_overrides(schedule_file, self.schedule.get_overrides()) {
+            return Err(PyValueError::new_err(format!("{}", err)));
+        }
+        Ok(())
+    }
+
+    // What is wrong in the data but was accepted because of the lenient flag, and what is risky
+    // in the configuration
+    pub fn get_data_warnings(&self) -> Vec<String> {
+        self.data_warnings.clone()
     }
 
     pub fn get_names(&self, lang: &str) -> PyResult<Vec<String>> {
         match self.handlers.get(lang) {
-            Some(handler) => Ok(handler.pokemon_names.clone()),
+            Some(handler) => Ok(handler.get_names()),
             None => Err(PyValueError::new_err(format!(
                 "Langage {} does not exist.",
                 lang
@@ -175,7 +486,20 @@ impl Pokedle {
         }
     }
 
-    pub fn get_previous_pokemon_to_guess_name(&self, lang: &str) -> PyResult<Option<String>> {
+    pub fn get_previous_pokemon_to_guess_name(&mut self, lang: &str) -> PyResult<Option<String>> {
+        self.update_daily_pokemon_if_needed(lang)?;
+        let handler = self.get_handler(lang)?;
+
+        match handler.previous_daily_pokemon_id {
+            Some(id) => match handler.get_pokemon_by_id(id) {
+                Ok(pokemon) => Ok(Some(pokemon.name.clone())),
+                Err(err) => Err(PyValueError::new_err(format!("{}", err))),
+            },
+            None => Ok(None),
+        }
+    }
+
+    pub fn get_pokemon_by_name(&self, lang: &str, pokemon_name: &str) -> PyResult<Pokemon> {
         let handler = match self.handlers.get(lang) {
             Some(handler) => handler,
             None => {
@@ -186,15 +510,194 @@ impl Pokedle {
             }
         };
 
-        match handler.previous_daily_pokemon_index {
-            // Ok to unwrap because the index is generated within the bould of this vector
-            Some(index) => Ok(Some(handler.pokemon_names.get(index).unwrap().clone())),
-            None => Ok(None),
+        match handler.get_pokemon_by_name(pokemon_name) {
+            Ok(pokemon) => Ok(pokemon.clone()),
+            Err(err) => Err(PyValueError::new_err(format!("{}", err))),
         }
     }
+}
 
-    pub fn get_pokemon_by_name(&self, lang: &str, pokemon_name: &str) -> PyResult<Pokemon> {
-        let handler = match self.handlers.get(lang) {
+// Not exposed to Python
+impl Pokedle {
+    pub fn with_clock(
+        pokle_dir: &str,
+        config: PokedleConfig,
+        clock: Arc<dyn Clock>,
+    ) -> PyResult<Self> {
+        let pokemons = match get_all_pokemons(PathBuf::from(pokle_dir)) {
+            Ok(pokemons) => pokemons,
+            Err(err) => return Err(PyValueError::new_err(format!("{}", err))),
+        };
+        Pokedle::with_pokemons(pokemons, config, clock)
+    }
+
+    pub fn from_bundle_with_clock(
+        bundle_file: &Path,
+        config: PokedleConfig,
+        clock: Arc<dyn Clock>,
+    ) -> PyResult<Self> {
+        let pokemons = match read_bundle(bundle_file)
+            .map_err(BundleError::from)
+            .and_then(Bundle::into_pokemons)
+        {
+            Ok(pokemons) => pokemons,
+            Err(err) => return Err(PyValueError::new_err(format!("{}", err))),
+        };
+        Pokedle::with_pokemons(pokemons, config, clock)
+    }
+
+    #[cfg(feature = "embedded-data")]
+    pub fn embedded_with_clock(config: PokedleConfig, clock: Arc<dyn Clock>) -> PyResult<Self> {
+        let bundle = match get_embedded_bundle() {
+            Ok(bundle) => bundle,
+            Err(err) => return Err(PyValueError::new_err(format!("{}", err))),
+        };
+        let pokemons = match bundle.into_pokemons() {
+            Ok(pokemons) => pokemons,
+            Err(err) => return Err(PyValueError::new_err(format!("{}", err))),
+        };
+        Pokedle::with_pokemons(pokemons, config, clock)
+    }
+
+    #[cfg(not(feature = "embedded-data"))]
+    pub fn embedded_with_clock(_config: PokedleConfig, _clock: Arc<dyn Clock>) -> PyResult<Self> {
+        Err(PyValueError::new_err(
+            "No pokle dir, and the library was built without the embedded-data feature",
+        ))
+    }
+
+    fn with_pokemons(
+        pokemons: BTreeMap<Lang, Vec<Pokemon>>,
+        config: PokedleConfig,
+        clock: Arc<dyn Clock>,
+    ) -> PyResult<Self> {
+        // All the languages must have the same pokemons, otherwise the daily pokemon could not exist in one of them
+        let mut all_pokemon_ids = pokemons.values().map(|p| get_pokemon_ids(p));
+        let pokemon_ids = match all_pokemon_ids.next() {
+            Some(pokemon_ids) => pokemon_ids,
+            None => return Err(PyValueError::new_err("No pokemon data")),
+        };
+        if pokemon_ids.is_empty() || all_pokemon_ids.any(|ids| ids != pokemon_ids) {
+            return Err(PyValueError::new_err("Incoherent data"));
+        }
+
+        let reference_lang = match config.reference_lang {
+            Some(lang) if !pokemons.contains_key(&lang) => {
+                let err = PokedleError::LangDoesNotExist(lang);
+                return Err(PyValueError::new_err(format!("{}", err)));
+            }
+            Some(lang) => lang,
+            None => pokemons.keys().next().cloned().unwrap_or_default(),
+        };
+        let mut data_warnings: Vec<String> = check_names(&pokemons)
+            .iter()
+            .map(|unknown_name| format!("{}", unknown_name))
+            .chain(
+                check_consistency(&pokemons, &reference_lang)
+                    .iter()
+                    .map(|inconsistency| format!("{}", inconsistency)),
+            )
+            .collect();
+        if !data_warnings.is_empty() && !config.lenient {
+            return Err(PyValueError::new_err(data_warnings.join("\n")));
+        }
+
+        let state = match &config.state_file {
+            Some(state_file) => match read_state(state_file) {
+                Ok(state) => state,
+                Err(err) => return Err(PyValueError::new_err(format!("{}", err))),
+            },
+            None => None,
+        };
+        let (saved_seed, history) = match state {
+            Some(state) => (Some(state.seed), state.history),
+            None => (None, BTreeMap::new()),
+        };
+        // Without a seed, the daily pokemon is still deterministic but only for this process
+        // (or all the processes sharing the state file)
+        let seed = config.seed.or(saved_seed).unwrap_or_else(rand::random);
+        if config.seed.is_none() && config.state_file.is_none() {
+            data_warnings.push(String::from(
+                "There is neither a seed nor a state file, the daily pokemon will change at every restart",
+            ));
+        }
+        let mut schedule = match DailySchedule::new(
+            pokemon_ids,
+            seed,
+            config.launch_date,
+            config.no_repeat_window,
+            history,
+        ) {
+            Ok(schedule) => schedule,
+            Err(err) => return Err(PyValueError::new_err(format!("{}", err))),
+        };
+
+        // Never in the data dir, it can be a submodule or be read only
+        let schedule_file = config.schedule_file.or_else(|| {
+            config
+                .state_file
+                .as_ref()
+                .map(|state_file| state_file.with_file_name("schedule.json"))
+        });
+        if let Some(schedule_file) = &schedule_file {
+            let overrides = match read_overrides(schedule_file) {
+                Ok(overrides) => overrides,
+                Err(err) => return Err(PyValueError::new_err(format!("{}", err))),
+            };
+            if let Err(err) = schedule.set_overrides(overrides) {
+                return Err(PyValueError::new_err(format!("{}", err)));
+            }
+        }
+
+        let mut handlers = BTreeMap::new();
+        for (lang, pokemons) in pokemons {
+            let reset_time = config.reset_times.get(&lang).cloned().unwrap_or_default();
+            let handler = PokemonHandler::new(pokemons, &mut schedule, reset_time, clock.clone());
+            handlers.insert(lang, handler);
+        }
+
+        let pokedle = Pokedle {
+            handlers,
+            schedule,
+            state_file: config.state_file,
+            schedule_file,
+            comparison_config: config.comparison,
+            data_warnings,
+        };
+        pokedle.save_state()?;
+        Ok(pokedle)
+    }
+
+    fn get_guess_result(
+        &self,
+        handler: &PokemonHandler,
+        pokemon_name: &str,
+        pokemon_to_guess: &Pokemon,
+        puzzle_id: PuzzleId,
+        mode: &GameMode,
+        units: UnitSystem,
+    ) -> Result<GuessResult, PokedleError> {
+        let game = GameDefinition::from_mode(mode, &self.comparison_config, units);
+        let (success, attributes) = handler.compare(pokemon_name, pokemon_to_guess, &game)?;
+        Ok(GuessResult {
+            puzzle_id,
+            success,
+            attributes,
+        })
+    }
+
+    fn get_handler(&self, lang: &str) -> PyResult<&PokemonHandler> {
+        match self.handlers.get(lang) {
+            Some(handler) => Ok(handler),
+            None => Err(PyValueError::new_err(format!(
+                "Langage {} does not exist.",
+                lang
+            ))),
+        }
+    }
+
+    fn update_daily_pokemon_if_needed(&mut self, lang: &str) -> PyResult<()> {
+        let handler = match self.handlers.get_mut(lang) {
             Some(handler) => handler,
             None => {
                 return Err(PyValueError::new_err(format!(
@@ -203,20 +706,41 @@ impl Pokedle {
                 )))
             }
         };
+        if handler.update_daily_pokemon_if_needed(&mut self.schedule) {
+            self.save_state()?;
+        }
+        Ok(())
+    }
 
-        match handler.get_pokemon_by_name(pokemon_name) {
-            Ok(pokemon) => Ok(pokemon.clone()),
+    fn save_state(&self) -> PyResult<()> {
+        let state_file = match &self.state_file {
+            Some(state_file) => state_file,
+            None => return Ok(()),
+        };
+        let state = DailyState {
+            seed: self.schedule.get_seed(),
+            history: self.schedule.get_history().clone(),
+        };
+        match write_state(state_file, &state) {
+            Ok(()) => Ok(()),
             Err(err) => Err(PyValueError::new_err(format!("{}", err))),
         }
     }
 }
 
+fn get_pokemon_ids(pokemons: &[Pokemon]) -> Vec<PokemonId> {
+    let mut ids: Vec<PokemonId> = pokemons.iter().map(|p| p.id).collect();
+    ids.sort();
+    ids
+}
+
 /*
     Tests
 */
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::Duration;
     use std::path::PathBuf;
 
     /*
@@ -226,78 +750,900 @@ mod tests {
     fn pokedle_creatation_simplified_data() {
         let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         d.push("small_test_data");
-        Pokedle::new(d.to_str().unwrap()).unwrap();
+        Pokedle::new(Some(d.to_str().unwrap()), None).unwrap();
     }
 
     #[test]
     fn pokedle_creatation_real_data() {
         let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         d.push("poke_data");
-        Pokedle::new(d.to_str().unwrap()).unwrap();
+        Pokedle::new(Some(d.to_str().unwrap()), None).unwrap();
+    }
+
+    #[test]
+    fn pokedle_creation_with_invalid_reset_time() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data");
+        let reset_times =
+            BTreeMap::from([(String::from("fr"), (String::from("Europe/Pariss"), 0))]);
+        assert!(get_reset_times(reset_times).is_err());
+        let reset_times =
+            BTreeMap::from([(String::from("fr"), (String::from("Europe/Paris"), 24))]);
+        assert!(get_reset_times(reset_times).is_err());
+        let reset_times = BTreeMap::from([(String::from("fr"), (String::from("Europe/Paris"), 0))]);
+        let config = PokedleConfig {
+            reset_times: get_reset_times(reset_times).unwrap(),
+            ..Default::default()
+        };
+        Pokedle::new(Some(d.to_str().unwrap()), Some(config)).unwrap();
     }
 
     #[test]
     fn game_scenario() {
         let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         d.push("small_test_data");
-        let mut pokedle = Pokedle::new(d.to_str().unwrap()).unwrap();
+        let mut pokedle = Pokedle::new(Some(d.to_str().unwrap()), None).unwrap();
         // Cheat a bit to know which pokemon we are trying to guess
         pokedle
             .handlers
             .get_mut("fr")
             .expect("Error in the test, not in the code")
-            .daily_pokemon_index = 0;
+            .daily_pokemon_id = 1;
 
         pokedle
             .get_names("lol")
             .expect_err("'lol' should not exist");
-        pokedle.get_names("fr").unwrap();
+        // In the order of the ids, whatever the order of the files
+        let names = pokedle.get_names("fr").unwrap();
+        assert_eq!(names.len(), 10);
+        assert_eq!(names[0], "Bulbizarre");
+        assert_eq!(names[9], "Chenipan");
 
         pokedle
-            .guess("lo", "Chenipan")
+            .guess(
+                "lo",
+                "Chenipan",
+                None,
+                GameMode::Classic,
+                UnitSystem::Metric,
+            )
             .expect_err("'lo' should not be a valid language");
         pokedle
-            .guess("fr", "Sacha")
+            .guess("fr", "Sacha", None, GameMode::Classic, UnitSystem::Metric)
             .expect_err("'Sacha' should not be a pokemon");
+        let result = pokedle
+            .guess(
+                "fr",
+                "Herbizarre",
+                None,
+                GameMode::Classic,
+                UnitSystem::Metric,
+            )
+            .unwrap();
+        assert!(!result.success);
         assert_eq!(
-            pokedle.guess("fr", "Herbizarre").unwrap(),
-            PokemonComparison {
-                success: false,
-                height: NumberComparison::Lower,
-                weight: NumberComparison::Lower,
-                types: TypesComparison::Equal,
-                color: ColorComparison::Equal,
-                generation: NumberComparison::Equal,
-            }
+            result.attributes,
+            vec![
+                AttributeResult {
+                    attribute: String::from("height"),
+                    guessed_value: Some(String::from("1 m")),
+                    verdict: Some(Verdict::Lower),
+                    distance: Some(NumberDistance {
+                        difference: -0.3,
+                        ratio: Some(0.7),
+                    }),
+                    slots: None,
+                },
+                AttributeResult {
+                    attribute: String::from("weight"),
+                    guessed_value: Some(String::from("13 kg")),
+                    verdict: Some(Verdict::Lower),
+                    distance: Some(NumberDistance {
+                        difference: -6.1,
+                        ratio: Some(0.5307692307692308),
+                    }),
+                    slots: None,
+                },
+                AttributeResult {
+                    attribute: String::from("types"),
+                    guessed_value: Some(String::from("Plante, Poison")),
+                    verdict: Some(Verdict::Equal),
+                    distance: None,
+                    slots: Some(TypesComparison {
+                        primary: TypeSlotComparison::Equal,
+                        secondary: TypeSlotComparison::Equal,
+                    }),
+                },
+                AttributeResult {
+                    attribute: String::from("color"),
+                    guessed_value: Some(String::from("Vert")),
+                    verdict: Some(Verdict::Equal),
+                    distance: None,
+                    slots: None,
+                },
+                AttributeResult {
+                    attribute: String::from("generation"),
+                    guessed_value: Some(String::from("1")),
+                    verdict: Some(Verdict::Equal),
+                    distance: Some(NumberDistance {
+                        difference: 0.0,
+                        ratio: Some(1.0),
+                    }),
+                    slots: None,
+                },
+                // Missing in the test data
+                AttributeResult {
+                    attribute: String::from("evolution_stage"),
+                    guessed_value: None,
+                    verdict: None,
+                    distance: None,
+                    slots: None,
+                },
+                AttributeResult {
+                    attribute: String::from("habitat"),
+                    guessed_value: None,
+                    verdict: None,
+                    distance: None,
+                    slots: None,
+                },
+                AttributeResult {
+                    attribute: String::from("shape"),
+                    guessed_value: None,
+                    verdict: None,
+                    distance: None,
+                    slots: None,
+                },
+                AttributeResult {
+                    attribute: String::from("status"),
+                    guessed_value: None,
+                    verdict: None,
+                    distance: None,
+                    slots: None,
+                },
+            ]
         );
+
+        let result = pokedle
+            .guess(
+                "fr",
+                "Bulbizarre",
+                None,
+                GameMode::Classic,
+                UnitSystem::Metric,
+            )
+            .unwrap();
+        assert!(result.success);
         assert_eq!(
-            pokedle.guess("fr", "Bulbizarre").unwrap(),
-            PokemonComparison {
-                success: true,
-                height: NumberComparison::Equal,
-                weight: NumberComparison::Equal,
-                types: TypesComparison::Equal,
-                color: ColorComparison::Equal,
-                generation: NumberComparison::Equal,
-            }
+            result.attributes[..5]
+                .iter()
+                .map(|attribute| (attribute.attribute.as_str(), attribute.verdict.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("height", Some(Verdict::Equal)),
+                ("weight", Some(Verdict::Equal)),
+                ("types", Some(Verdict::Equal)),
+                ("color", Some(Verdict::Equal)),
+                ("generation", Some(Verdict::Equal)),
+            ]
+        );
+        assert_eq!(
+            result.attributes[0].distance,
+            Some(NumberDistance {
+                difference: 0.0,
+                ratio: Some(1.0),
+            })
+        );
+    }
+
+    #[test]
+    fn same_daily_pokemon_in_every_lang() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data");
+        let clock = FakeClock::new(Utc.with_ymd_and_hms(2023, 10, 14, 12, 0, 0).unwrap());
+        let config = PokedleConfig {
+            seed: Some(42),
+            ..Default::default()
+        };
+        let pokedle = Pokedle::with_clock(d.to_str().unwrap(), config, Arc::new(clock)).unwrap();
+
+        let fr_pokemon = pokedle.handlers["fr"].get_daily_pokemon().unwrap();
+        let de_pokemon = pokedle.handlers["de"].get_daily_pokemon().unwrap();
+        assert_eq!(fr_pokemon.id, de_pokemon.id);
+        assert_ne!(fr_pokemon.name, de_pokemon.name);
+    }
+
+    #[test]
+    fn reset_time_by_lang() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data");
+        let clock = FakeClock::new(Utc.with_ymd_and_hms(2023, 10, 14, 12, 0, 0).unwrap());
+        let config = PokedleConfig {
+            seed: Some(42),
+            reset_times: BTreeMap::from([(
+                String::from("fr"),
+                ResetTime::new("Europe/Paris", 0).unwrap(),
+            )]),
+            ..Default::default()
+        };
+        let mut pokedle =
+            Pokedle::with_clock(d.to_str().unwrap(), config, Arc::new(clock.clone())).unwrap();
+
+        // Midnight in Paris, the french pokemon changes but not the german one
+        clock.set(Utc.with_ymd_and_hms(2023, 10, 14, 22, 0, 0).unwrap());
+        pokedle
+            .guess(
+                "fr",
+                "Chenipan",
+                None,
+                GameMode::Classic,
+                UnitSystem::Metric,
+            )
+            .unwrap();
+        pokedle
+            .guess("de", "Raupy", None, GameMode::Classic, UnitSystem::Metric)
+            .unwrap();
+        assert_eq!(
+            pokedle.handlers["fr"].puzzle_date,
+            NaiveDate::from_ymd_opt(2023, 10, 15).unwrap()
+        );
+        assert_eq!(
+            pokedle.handlers["fr"]
+                .reset_time
+                .get_reset_datetime(pokedle.handlers["fr"].puzzle_date),
+            Utc.with_ymd_and_hms(2023, 10, 14, 22, 0, 0).unwrap()
+        );
+        assert_eq!(
+            pokedle.handlers["de"].puzzle_date,
+            NaiveDate::from_ymd_opt(2023, 10, 14).unwrap()
+        );
+        assert_eq!(
+            pokedle.handlers["fr"].previous_daily_pokemon_id,
+            Some(
+                pokedle
+                    .schedule
+                    .get_pokemon_id_for_date(NaiveDate::from_ymd_opt(2023, 10, 14).unwrap())
+            )
+        );
+        assert_eq!(
+            pokedle.handlers["de"].previous_daily_pokemon_id,
+            Some(
+                pokedle
+                    .schedule
+                    .get_pokemon_id_for_date(NaiveDate::from_ymd_opt(2023, 10, 13).unwrap())
+            )
         );
     }
 
+    #[test]
+    fn puzzle_expired() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data");
+        let clock = FakeClock::new(Utc.with_ymd_and_hms(2023, 10, 14, 12, 0, 0).unwrap());
+        let config = PokedleConfig {
+            launch_date: NaiveDate::from_ymd_opt(2023, 10, 10),
+            ..Default::default()
+        };
+        let mut pokedle =
+            Pokedle::with_clock(d.to_str().unwrap(), config, Arc::new(clock.clone())).unwrap();
+
+        assert_eq!(pokedle.get_puzzle_id("fr").unwrap(), 4);
+        assert_eq!(
+            pokedle
+                .guess(
+                    "fr",
+                    "Chenipan",
+                    None,
+                    GameMode::Classic,
+                    UnitSystem::Metric
+                )
+                .unwrap()
+                .puzzle_id,
+            4
+        );
+        assert_eq!(
+            pokedle
+                .guess(
+                    "fr",
+                    "Chenipan",
+                    Some(4),
+                    GameMode::Classic,
+                    UnitSystem::Metric
+                )
+                .unwrap()
+                .puzzle_id,
+            4
+        );
+
+        // The pokemon changed while the player was playing
+        clock.advance(Duration::days(1));
+        pokedle
+            .guess(
+                "fr",
+                "Chenipan",
+                Some(4),
+                GameMode::Classic,
+                UnitSystem::Metric,
+            )
+            .expect_err("The puzzle 4 is over");
+        assert_eq!(
+            pokedle
+                .guess(
+                    "fr",
+                    "Chenipan",
+                    Some(5),
+                    GameMode::Classic,
+                    UnitSystem::Metric
+                )
+                .unwrap()
+                .puzzle_id,
+            5
+        );
+        pokedle
+            .get_puzzle_id("lol")
+            .expect_err("'lol' should not exist");
+    }
+
+    #[test]
+    fn archive_mode() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data");
+        let clock = FakeClock::new(Utc.with_ymd_and_hms(2023, 10, 14, 12, 0, 0).unwrap());
+        let config = PokedleConfig {
+            launch_date: NaiveDate::from_ymd_opt(2023, 10, 10),
+            ..Default::default()
+        };
+        let mut pokedle =
+            Pokedle::with_clock(d.to_str().unwrap(), config, Arc::new(clock)).unwrap();
+
+        let past_puzzles = pokedle.get_past_puzzles("fr").unwrap();
+        assert_eq!(
+            past_puzzles,
+            (0..4)
+                .rev()
+                .map(|i| (i, NaiveDate::from_ymd_opt(2023, 10, 10 + i as u32).unwrap()))
+                .collect::<Vec<(PuzzleId, NaiveDate)>>()
+        );
+
+        for (puzzle_id, date) in past_puzzles {
+            let pokemon_id = pokedle.schedule.get_pokemon_id_for_date(date);
+            let pokemon_name = pokedle.handlers["fr"]
+                .get_pokemon_by_id(pokemon_id)
+                .unwrap()
+                .name
+                .clone();
+
+            let result = pokedle
+                .guess_for_puzzle(
+                    "fr",
+                    puzzle_id,
+                    &pokemon_name,
+                    GameMode::Classic,
+                    UnitSystem::Metric,
+                )
+                .unwrap();
+            assert_eq!(result.puzzle_id, puzzle_id);
+            assert!(result.success);
+
+            let result = pokedle
+                .guess_for_date(
+                    "fr",
+                    date,
+                    &pokemon_name,
+                    GameMode::Classic,
+                    UnitSystem::Metric,
+                )
+                .unwrap();
+            assert_eq!(result.puzzle_id, puzzle_id);
+            assert!(result.success);
+        }
+
+        // The puzzle of today is available, but not the ones of the next days or before the launch
+        pokedle
+            .guess_for_puzzle("fr", 4, "Chenipan", GameMode::Classic, UnitSystem::Metric)
+            .unwrap();
+        pokedle
+            .guess_for_puzzle("fr", 5, "Chenipan", GameMode::Classic, UnitSystem::Metric)
+            .expect_err("The puzzle of tomorrow is a secret");
+        pokedle
+            .guess_for_puzzle("fr", -1, "Chenipan", GameMode::Classic, UnitSystem::Metric)
+            .expect_err("There is no puzzle before the launch");
+        pokedle
+            .guess_for_puzzle("lol", 1, "Chenipan", GameMode::Classic, UnitSystem::Metric)
+            .expect_err("'lol' should not exist");
+        pokedle
+            .guess_for_puzzle("fr", 1, "Sacha", GameMode::Classic, UnitSystem::Metric)
+            .expect_err("'Sacha' should not be a pokemon");
+    }
+
+    #[test]
+    fn archive_mode_without_launch_date() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data");
+        let clock = FakeClock::new(Utc.with_ymd_and_hms(2023, 10, 14, 12, 0, 0).unwrap());
+        let mut pokedle = Pokedle::with_clock(
+            d.to_str().unwrap(),
+            PokedleConfig::default(),
+            Arc::new(clock.clone()),
+        )
+        .unwrap();
+
+        // The days before the first one played are not in the archive
+        let puzzle_id = pokedle.get_puzzle_id("fr").unwrap();
+        assert_eq!(pokedle.get_past_puzzles("fr").unwrap(), vec![]);
+        pokedle
+            .guess_for_puzzle(
+                "fr",
+                puzzle_id - 1,
+                "Chenipan",
+                GameMode::Classic,
+                UnitSystem::Metric,
+            )
+            .expect_err("The day before the first one played is not in the archive");
+
+        clock.advance(Duration::days(2));
+        assert_eq!(
+            pokedle.get_past_puzzles("fr").unwrap(),
+            vec![
+                (
+                    puzzle_id + 1,
+                    NaiveDate::from_ymd_opt(2023, 10, 15).unwrap()
+                ),
+                (puzzle_id, NaiveDate::from_ymd_opt(2023, 10, 14).unwrap())
+            ]
+        );
+    }
+
+    #[test]
+    fn previous_pokemon_after_rollover() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data");
+        let clock = FakeClock::new(Utc.with_ymd_and_hms(2023, 10, 14, 12, 0, 0).unwrap());
+        let mut pokedle = Pokedle::with_clock(
+            d.to_str().unwrap(),
+            PokedleConfig::default(),
+            Arc::new(clock.clone()),
+        )
+        .unwrap();
+        let first_pokemon_name = pokedle.handlers["fr"]
+            .get_daily_pokemon()
+            .unwrap()
+            .name
+            .clone();
+
+        clock.advance(Duration::days(1));
+        pokedle
+            .guess(
+                "fr",
+                "Chenipan",
+                None,
+                GameMode::Classic,
+                UnitSystem::Metric,
+            )
+            .unwrap();
+        assert_eq!(
+            pokedle.get_previous_pokemon_to_guess_name("fr").unwrap(),
+            Some(first_pokemon_name)
+        );
+    }
+
+    #[test]
+    fn previous_pokemon_after_rollover_without_guess() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data");
+        let clock = FakeClock::new(Utc.with_ymd_and_hms(2023, 10, 14, 12, 0, 0).unwrap());
+        let mut pokedle = Pokedle::with_clock(
+            d.to_str().unwrap(),
+            PokedleConfig::default(),
+            Arc::new(clock.clone()),
+        )
+        .unwrap();
+        let first_pokemon_name = pokedle.handlers["fr"]
+            .get_daily_pokemon()
+            .unwrap()
+            .name
+            .clone();
+
+        // Nobody guessed since the reset, the previous pokemon must still be the one of yesterday
+        clock.advance(Duration::days(1));
+        assert_eq!(
+            pokedle.get_previous_pokemon_to_guess_name("fr").unwrap(),
+            Some(first_pokemon_name)
+        );
+    }
+
+    #[test]
+    fn previous_pokemon_after_restart() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data");
+        let clock = FakeClock::new(Utc.with_ymd_and_hms(2023, 10, 14, 12, 0, 0).unwrap());
+        let config = PokedleConfig {
+            seed: Some(42),
+            ..Default::default()
+        };
+        let pokedle =
+            Pokedle::with_clock(d.to_str().unwrap(), config, Arc::new(clock.clone())).unwrap();
+        let first_pokemon_name = pokedle.handlers["fr"]
+            .get_daily_pokemon()
+            .unwrap()
+            .name
+            .clone();
+
+        clock.advance(Duration::days(1));
+        let config = PokedleConfig {
+            seed: Some(42),
+            ..Default::default()
+        };
+        let mut pokedle =
+            Pokedle::with_clock(d.to_str().unwrap(), config, Arc::new(clock)).unwrap();
+        assert_eq!(
+            pokedle.get_previous_pokemon_to_guess_name("fr").unwrap(),
+            Some(first_pokemon_name)
+        );
+    }
+
+    #[test]
+    fn state_file() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data");
+        let mut state_file = std::env::temp_dir();
+        state_file.push("pokedle_state_file_test.json");
+        let _ = std::fs::remove_file(&state_file);
+
+        // No seed given, the random one is saved and reused after a restart
+        let clock = FakeClock::new(Utc.with_ymd_and_hms(2023, 10, 14, 12, 0, 0).unwrap());
+        let config = PokedleConfig {
+            state_file: Some(state_file.clone()),
+            ..Default::default()
+        };
+        let mut pokedle =
+            Pokedle::with_clock(d.to_str().unwrap(), config, Arc::new(clock.clone())).unwrap();
+        let first_pokemon_id = pokedle.handlers["fr"].daily_pokemon_id;
+        clock.advance(Duration::days(1));
+        pokedle.get_puzzle_id("fr").unwrap();
+        let second_pokemon_id = pokedle.handlers["fr"].daily_pokemon_id;
+
+        let state = read_state(&state_file).unwrap().unwrap();
+        assert_eq!(state.seed, pokedle.schedule.get_seed());
+        assert_eq!(
+            state.history,
+            BTreeMap::from([
+                (
+                    NaiveDate::from_ymd_opt(2023, 10, 14).unwrap(),
+                    first_pokemon_id
+                ),
+                (
+                    NaiveDate::from_ymd_opt(2023, 10, 15).unwrap(),
+                    second_pokemon_id
+                ),
+            ])
+        );
+
+        let config = PokedleConfig {
+            state_file: Some(state_file.clone()),
+            ..Default::default()
+        };
+        let pokedle =
+            Pokedle::with_clock(d.to_str().unwrap(), config, Arc::new(clock.clone())).unwrap();
+        assert_eq!(pokedle.handlers["fr"].daily_pokemon_id, second_pokemon_id);
+        assert_eq!(
+            pokedle.handlers["fr"].previous_daily_pokemon_id,
+            Some(first_pokemon_id)
+        );
+
+        // The history wins over the seed
+        let mut state = read_state(&state_file).unwrap().unwrap();
+        let other_pokemon_id = pokedle.handlers["fr"]
+            .pokemons
+            .keys()
+            .copied()
+            .find(|id| *id != second_pokemon_id)
+            .unwrap();
+        state.history.insert(
+            NaiveDate::from_ymd_opt(2023, 10, 15).unwrap(),
+            other_pokemon_id,
+        );
+        write_state(&state_file, &state).unwrap();
+        let config = PokedleConfig {
+            state_file: Some(state_file.clone()),
+            ..Default::default()
+        };
+        let pokedle = Pokedle::with_clock(d.to_str().unwrap(), config, Arc::new(clock)).unwrap();
+        assert_eq!(pokedle.handlers["fr"].daily_pokemon_id, other_pokemon_id);
+
+        std::fs::remove_file(state_file).unwrap();
+    }
+
+    #[test]
+    fn bundle() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data");
+        let mut bundle_dir = std::env::temp_dir();
+        bundle_dir.push("pokedle_bundle_test");
+        let _ = std::fs::remove_dir_all(&bundle_dir);
+        std::fs::create_dir_all(&bundle_dir).unwrap();
+        let bundle_file = bundle_dir.join("bundle.json");
+        create_bundle(d.clone(), bundle_file.clone()).unwrap();
+
+        // The same game as with the directory
+        let clock = Arc::new(FakeClock::new(
+            Utc.with_ymd_and_hms(2023, 10, 14, 12, 0, 0).unwrap(),
+        ));
+        let config = || PokedleConfig {
+            seed: Some(42),
+            ..Default::default()
+        };
+        let from_dir = Pokedle::with_clock(d.to_str().unwrap(), config(), clock.clone()).unwrap();
+        let mut from_bundle =
+            Pokedle::from_bundle_with_clock(&bundle_file, config(), clock).unwrap();
+        assert_eq!(
+            from_bundle.get_names("de").unwrap(),
+            from_dir.get_names("de").unwrap()
+        );
+        assert_eq!(
+            from_bundle.handlers["fr"].daily_pokemon_id,
+            from_dir.handlers["fr"].daily_pokemon_id
+        );
+
+        // Nothing is written next to the bundle
+        assert_eq!(from_bundle.schedule_file, None);
+        from_bundle
+            .set_daily_override(NaiveDate::from_ymd_opt(2023, 10, 20).unwrap(), 3)
+            .expect_err("There is no schedule file");
+        assert!(!bundle_dir.join("schedule.json").exists());
+
+        std::fs::remove_dir_all(bundle_dir).unwrap();
+    }
+
+    #[test]
+    fn embedded_data() {
+        let clock = Arc::new(FakeClock::new(
+            Utc.with_ymd_and_hms(2023, 10, 14, 12, 0, 0).unwrap(),
+        ));
+        let pokedle = Pokedle::embedded_with_clock(Default::default(), clock);
+        if cfg!(feature = "embedded-data") {
+            let pokedle = pokedle.unwrap();
+            assert!(!pokedle.handlers.is_empty());
+            assert_eq!(pokedle.schedule_file, None);
+        } else {
+            assert!(pokedle.is_err());
+        }
+    }
+
+    #[test]
+    fn inconsistent_data() {
+        // A copy of the test data where Raupy is heavier than Chenipan, and Chenipan has a typo in
+        // its color
+        let mut d = std::env::temp_dir();
+        d.push("pokedle_inconsistent_data");
+        let _ = std::fs::remove_dir_all(&d);
+        let small_test_data = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("small_test_data");
+        for file in ["fr/metadata.json", "de/metadata.json"]
+            .into_iter()
+            .map(String::from)
+            .chain((1..=10).map(|id| format!("fr/pokedle/{}.json", id)))
+            .chain((1..=10).map(|id| format!("de/pokedle/{}.json", id)))
+        {
+            let from = small_test_data.join("generated_data").join(&file);
+            let to = d.join("generated_data").join(&file);
+            std::fs::create_dir_all(to.parent().unwrap()).unwrap();
+            std::fs::copy(from, to).unwrap();
+        }
+        let raupy_file = d.join("generated_data/de/pokedle/10.json");
+        let raupy = std::fs::read_to_string(&raupy_file).unwrap();
+        std::fs::write(&raupy_file, raupy.replace("2.9", "3.0")).unwrap();
+        let chenipan_file = d.join("generated_data/fr/pokedle/10.json");
+        let chenipan = std::fs::read_to_string(&chenipan_file).unwrap();
+        std::fs::write(&chenipan_file, chenipan.replace("Vert", "Vret")).unwrap();
+
+        let clock = Arc::new(FakeClock::new(
+            Utc.with_ymd_and_hms(2023, 10, 14, 12, 0, 0).unwrap(),
+        ));
+        assert!(
+            Pokedle::with_clock(d.to_str().unwrap(), Default::default(), clock.clone()).is_err()
+        );
+
+        let config = PokedleConfig {
+            seed: Some(42),
+            reference_lang: Some(String::from("fr")),
+            lenient: true,
+            ..Default::default()
+        };
+        let pokedle = Pokedle::with_clock(d.to_str().unwrap(), config, clock.clone()).unwrap();
+        assert_eq!(
+            pokedle.get_data_warnings(),
+            vec![
+                String::from("The pokemon 10 has the unknown color Vret in fr"),
+                String::from("The pokemon 10 has the weight 3 in de but 2.9 in fr")
+            ]
+        );
+
+        let config = PokedleConfig {
+            reference_lang: Some(String::from("en")),
+            lenient: true,
+            ..Default::default()
+        };
+        assert!(Pokedle::with_clock(d.to_str().unwrap(), config, clock).is_err());
+
+        std::fs::remove_dir_all(d).unwrap();
+    }
+
+    #[test]
+    fn warning_without_seed() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data");
+        let clock = Arc::new(FakeClock::new(
+            Utc.with_ymd_and_hms(2023, 10, 14, 12, 0, 0).unwrap(),
+        ));
+
+        let pokedle =
+            Pokedle::with_clock(d.to_str().unwrap(), Default::default(), clock.clone()).unwrap();
+        assert_eq!(
+            pokedle.get_data_warnings(),
+            vec![String::from(
+                "There is neither a seed nor a state file, the daily pokemon will change at every restart"
+            )]
+        );
+
+        let config = PokedleConfig {
+            seed: Some(42),
+            ..Default::default()
+        };
+        let pokedle = Pokedle::with_clock(d.to_str().unwrap(), config, clock).unwrap();
+        assert!(pokedle.get_data_warnings().is_empty());
+    }
+
+    #[test]
+    fn daily_override() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data");
+        let mut schedule_file = std::env::temp_dir();
+        schedule_file.push("pokedle_daily_override_test.json");
+        let _ = std::fs::remove_file(&schedule_file);
+
+        let today = NaiveDate::from_ymd_opt(2023, 10, 14).unwrap();
+        let tomorrow = today.succ_opt().unwrap();
+        let clock = FakeClock::new(Utc.with_ymd_and_hms(2023, 10, 14, 12, 0, 0).unwrap());
+        let config = PokedleConfig {
+            seed: Some(42),
+            schedule_file: Some(schedule_file.clone()),
+            ..Default::default()
+        };
+        let mut pokedle =
+            Pokedle::with_clock(d.to_str().unwrap(), config, Arc::new(clock.clone())).unwrap();
+        let random_pokemon_id = pokedle.handlers["fr"].daily_pokemon_id;
+        let chosen_pokemon_id = pokedle.handlers["fr"]
+            .pokemons
+            .keys()
+            .copied()
+            .find(|id| *id != random_pokemon_id)
+            .unwrap();
+
+        // Today's puzzle is already played
+        pokedle
+            .set_daily_override(today, chosen_pokemon_id)
+            .expect_err("Today's puzzle can't change");
+        assert_eq!(pokedle.handlers["fr"].daily_pokemon_id, random_pokemon_id);
+
+        // Tomorrow's pokemon is chosen, in every language
+        pokedle
+            .set_daily_override(tomorrow, chosen_pokemon_id)
+            .unwrap();
+        pokedle
+            .set_daily_override(tomorrow, 1000)
+            .expect_err("This pokemon does not exist");
+        clock.advance(Duration::days(1));
+        pokedle.get_puzzle_id("fr").unwrap();
+        pokedle.get_puzzle_id("de").unwrap();
+        assert_eq!(pokedle.handlers["fr"].daily_pokemon_id, chosen_pokemon_id);
+        assert_eq!(pokedle.handlers["de"].daily_pokemon_id, chosen_pokemon_id);
+        assert_eq!(
+            pokedle.handlers["fr"].previous_daily_pokemon_id,
+            Some(random_pokemon_id)
+        );
+
+        // The overrides are loaded from the schedule file, whatever the seed
+        let config = PokedleConfig {
+            seed: Some(43),
+            schedule_file: Some(schedule_file.clone()),
+            ..Default::default()
+        };
+        let pokedle =
+            Pokedle::with_clock(d.to_str().unwrap(), config, Arc::new(clock.clone())).unwrap();
+        assert_eq!(pokedle.handlers["fr"].daily_pokemon_id, chosen_pokemon_id);
+
+        // Without a schedule file, it is next to the state file
+        let mut state_file = std::env::temp_dir();
+        state_file.push("pokedle_daily_override_dir");
+        std::fs::create_dir_all(&state_file).unwrap();
+        state_file.push("state.json");
+        let config = PokedleConfig {
+            state_file: Some(state_file.clone()),
+            ..Default::default()
+        };
+        let mut pokedle =
+            Pokedle::with_clock(d.to_str().unwrap(), config, Arc::new(clock.clone())).unwrap();
+        pokedle
+            .set_daily_override(tomorrow.succ_opt().unwrap(), chosen_pokemon_id)
+            .unwrap();
+        assert!(state_file.with_file_name("schedule.json").exists());
+        std::fs::remove_dir_all(state_file.parent().unwrap()).unwrap();
+
+        // A schedule file with an unknown pokemon is refused
+        let overrides = BTreeMap::from([(today, 1000)]);
+        write_overrides(&schedule_file, &overrides).unwrap();
+        let config = PokedleConfig {
+            schedule_file: Some(schedule_file.clone()),
+            ..Default::default()
+        };
+        assert!(Pokedle::with_clock(d.to_str().unwrap(), config, Arc::new(clock)).is_err());
+
+        std::fs::remove_file(schedule_file).unwrap();
+    }
+
     /*
      ** PokemonHandler tests
      */
     #[test]
     fn pokemon_handler_creation() {
-        let (names, pokemons) = generate_dummy_pokemon_data();
-        let handler = PokemonHandler::new(names, pokemons.clone());
-        let daily_pokemon = handler.get_daily_pokemon();
-        assert!(pokemons.contains(daily_pokemon));
+        let clock = FakeClock::new(Utc.with_ymd_and_hms(2023, 10, 14, 12, 0, 0).unwrap());
+        let (handler, schedule) = generate_dummy_handler(ResetTime::default(), clock);
+        assert_eq!(
+            handler.puzzle_date,
+            NaiveDate::from_ymd_opt(2023, 10, 14).unwrap()
+        );
+        assert_eq!(
+            handler.daily_pokemon_id,
+            schedule.get_pokemon_id_for_date(handler.puzzle_date)
+        );
+        assert_eq!(
+            handler.previous_daily_pokemon_id,
+            Some(schedule.get_pokemon_id_for_date(NaiveDate::from_ymd_opt(2023, 10, 13).unwrap()))
+        );
+        assert_eq!(
+            handler.reset_time.get_reset_datetime(handler.puzzle_date),
+            Utc.with_ymd_and_hms(2023, 10, 14, 6, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn pokemon_handler_creation_before_the_reset_time() {
+        // It is still the puzzle of the day before
+        let clock = FakeClock::new(Utc.with_ymd_and_hms(2023, 10, 14, 5, 59, 59).unwrap());
+        let (handler, schedule) = generate_dummy_handler(ResetTime::default(), clock);
+        assert_eq!(
+            handler.puzzle_date,
+            NaiveDate::from_ymd_opt(2023, 10, 13).unwrap()
+        );
+        assert_eq!(
+            handler.daily_pokemon_id,
+            schedule.get_pokemon_id_for_date(handler.puzzle_date)
+        );
+    }
+
+    #[test]
+    fn pokemon_handler_get_pokemon_by_id() {
+        let clock = FakeClock::new(Utc.with_ymd_and_hms(2023, 10, 14, 12, 0, 0).unwrap());
+        let (handler, _) = generate_dummy_handler(ResetTime::default(), clock);
+        let pokemons = generate_dummy_pokemon_data();
+
+        for pokemon in pokemons.iter() {
+            assert_eq!(
+                handler
+                    .get_pokemon_by_id(pokemon.id)
+                    .expect("Oh no, the pokemon is not found"),
+                pokemon
+            );
+        }
+        handler
+            .get_pokemon_by_id(42)
+            .expect_err("There is no pokemon with the id 42");
     }
 
     #[test]
     fn pokemon_handler_get_pokemon_by_name() {
-        let (names, pokemons) = generate_dummy_pokemon_data();
-        let handler = PokemonHandler::new(names, pokemons.clone());
+        let clock = FakeClock::new(Utc.with_ymd_and_hms(2023, 10, 14, 12, 0, 0).unwrap());
+        let (handler, _) = generate_dummy_handler(ResetTime::default(), clock);
+        let pokemons = generate_dummy_pokemon_data();
 
         assert_eq!(
             handler
@@ -347,88 +1693,199 @@ mod tests {
 
     #[test]
     fn pokemon_handler_update() {
-        let (names, pokemons) = generate_dummy_pokemon_data();
-        let mut handler = PokemonHandler::new(names, pokemons.clone());
-        let first_index = handler.daily_pokemon_index;
-
-        // Do it a lot, to be sure that's not just luck, theorically it is still possible but it would really improbable
-        for _ in [0..100] {
-            handler.update_daily_pokemon_if_needed();
-            // The creation just happened, so it should not change
-            assert_eq!(first_index, handler.daily_pokemon_index);
-        }
+        let clock = FakeClock::new(Utc.with_ymd_and_hms(2023, 10, 14, 12, 0, 0).unwrap());
+        let (mut handler, mut schedule) =
+            generate_dummy_handler(ResetTime::default(), clock.clone());
+        let first_id = handler.daily_pokemon_id;
 
-        // Change the last update so it is at least one day in the past, now it should change
-        let current_datetime = Utc::now();
-        handler.last_pokemon_update = Utc
-            .with_ymd_and_hms(
-                current_datetime.year() - 1,
-                current_datetime.month(),
-                current_datetime.day(),
-                6,
-                0,
-                0,
-            )
-            .unwrap();
-        // Do it until it is different (because there is random) with a limitation to not have an infinite loop if it fails
-        let mut index_changed = false;
-        for _ in [0..100] {
-            handler.update_daily_pokemon_if_needed();
-            // The creation just happened, so it should not change
-            if first_index != handler.daily_pokemon_index {
-                assert_eq!(first_index, handler.previous_daily_pokemon_index.unwrap());
-                index_changed = true;
-                break;
-            }
-        }
-        assert!(index_changed);
+        // Just before the reset time, nothing changes
+        clock.set(Utc.with_ymd_and_hms(2023, 10, 15, 5, 59, 59).unwrap());
+        handler.update_daily_pokemon_if_needed(&mut schedule);
+        assert_eq!(first_id, handler.daily_pokemon_id);
+
+        clock.advance(Duration::seconds(1));
+        handler.update_daily_pokemon_if_needed(&mut schedule);
+        assert_eq!(handler.previous_daily_pokemon_id, Some(first_id));
+        assert_eq!(
+            handler.daily_pokemon_id,
+            schedule.get_pokemon_id_for_date(NaiveDate::from_ymd_opt(2023, 10, 15).unwrap())
+        );
+    }
+
+    #[test]
+    fn pokemon_handler_update_after_several_days() {
+        let clock = FakeClock::new(Utc.with_ymd_and_hms(2023, 10, 14, 12, 0, 0).unwrap());
+        let (mut handler, mut schedule) =
+            generate_dummy_handler(ResetTime::default(), clock.clone());
+
+        // Nobody played for 3 days, the previous pokemon is still the one of yesterday
+        clock.advance(Duration::days(3));
+        handler.update_daily_pokemon_if_needed(&mut schedule);
+        assert_eq!(
+            handler.daily_pokemon_id,
+            schedule.get_pokemon_id_for_date(NaiveDate::from_ymd_opt(2023, 10, 17).unwrap())
+        );
+        assert_eq!(
+            handler.previous_daily_pokemon_id,
+            Some(schedule.get_pokemon_id_for_date(NaiveDate::from_ymd_opt(2023, 10, 16).unwrap()))
+        );
+    }
+
+    #[test]
+    fn pokemon_handler_update_does_not_drift() {
+        let clock = FakeClock::new(Utc.with_ymd_and_hms(2023, 10, 14, 12, 0, 0).unwrap());
+        let (mut handler, mut schedule) =
+            generate_dummy_handler(ResetTime::default(), clock.clone());
+
+        // Nobody played before the evening, the next reset is still in the morning
+        clock.set(Utc.with_ymd_and_hms(2023, 10, 15, 21, 0, 0).unwrap());
+        handler.update_daily_pokemon_if_needed(&mut schedule);
+        assert_eq!(
+            handler.reset_time.get_reset_datetime(handler.puzzle_date),
+            Utc.with_ymd_and_hms(2023, 10, 15, 6, 0, 0).unwrap()
+        );
+        clock.set(Utc.with_ymd_and_hms(2023, 10, 16, 6, 0, 0).unwrap());
+        handler.update_daily_pokemon_if_needed(&mut schedule);
+        assert_eq!(
+            handler.puzzle_date,
+            NaiveDate::from_ymd_opt(2023, 10, 16).unwrap()
+        );
+    }
+
+    #[test]
+    fn pokemon_handler_update_on_leap_day() {
+        let clock = FakeClock::new(Utc.with_ymd_and_hms(2024, 2, 28, 12, 0, 0).unwrap());
+        let (mut handler, mut schedule) =
+            generate_dummy_handler(ResetTime::default(), clock.clone());
+
+        clock.advance(Duration::days(1));
+        handler.update_daily_pokemon_if_needed(&mut schedule);
+        assert_eq!(
+            handler.puzzle_date,
+            NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+        );
+        assert_eq!(
+            handler.daily_pokemon_id,
+            schedule.get_pokemon_id_for_date(handler.puzzle_date)
+        );
+    }
+
+    #[test]
+    fn pokemon_handler_update_on_dst_change() {
+        // In Paris, the night of the 31 of march 2024 lasts one hour less
+        let reset_time = ResetTime::new("Europe/Paris", 0).unwrap();
+        let clock = FakeClock::new(Utc.with_ymd_and_hms(2024, 3, 30, 12, 0, 0).unwrap());
+        let (mut handler, mut schedule) = generate_dummy_handler(reset_time, clock.clone());
+
+        // Midnight on the 31 is still in winter time
+        clock.set(Utc.with_ymd_and_hms(2024, 3, 30, 23, 0, 0).unwrap());
+        handler.update_daily_pokemon_if_needed(&mut schedule);
+        assert_eq!(
+            handler.puzzle_date,
+            NaiveDate::from_ymd_opt(2024, 3, 31).unwrap()
+        );
+
+        // But midnight on the 1st of april is in summer time
+        clock.set(Utc.with_ymd_and_hms(2024, 3, 31, 21, 59, 59).unwrap());
+        handler.update_daily_pokemon_if_needed(&mut schedule);
+        assert_eq!(
+            handler.puzzle_date,
+            NaiveDate::from_ymd_opt(2024, 3, 31).unwrap()
+        );
+        clock.advance(Duration::seconds(1));
+        handler.update_daily_pokemon_if_needed(&mut schedule);
+        assert_eq!(
+            handler.puzzle_date,
+            NaiveDate::from_ymd_opt(2024, 4, 1).unwrap()
+        );
+    }
+
+    fn generate_dummy_handler(
+        reset_time: ResetTime,
+        clock: FakeClock,
+    ) -> (PokemonHandler, DailySchedule) {
+        let pokemons = generate_dummy_pokemon_data();
+        let mut schedule =
+            DailySchedule::new(get_pokemon_ids(&pokemons), 42, None, None, BTreeMap::new())
+                .unwrap();
+        let handler = PokemonHandler::new(pokemons, &mut schedule, reset_time, Arc::new(clock));
+        (handler, schedule)
     }
 
-    fn generate_dummy_pokemon_data() -> (Vec<String>, Vec<Pokemon>) {
+    fn generate_dummy_pokemon_data() -> Vec<Pokemon> {
         let pokemons = vec![
             Pokemon {
+                id: 10,
                 name: String::from("Chrysacier"),
-                height: Height(0.7),
-                weight: Weight(9.9),
-                types: vec![Type(String::from("Insecte"))],
-                color: Color(String::from("Vert")),
+                height: Height(7),
+                weight: Weight(99),
+                types: vec![Type::from_name("Insecte")],
+                color: Color::from_name("Vert"),
                 generation: Generation(1),
+                evolution_stage: None,
+                habitat: None,
+                shape: None,
+                status: None,
+                base_stats: None,
             },
             Pokemon {
+                id: 11,
                 name: String::from("ChrysacierBis"),
-                height: Height(0.7),
-                weight: Weight(9.9),
-                types: vec![Type(String::from("Insecte"))],
-                color: Color(String::from("Vert")),
+                height: Height(7),
+                weight: Weight(99),
+                types: vec![Type::from_name("Insecte")],
+                color: Color::from_name("Vert"),
                 generation: Generation(12),
+                evolution_stage: None,
+                habitat: None,
+                shape: None,
+                status: None,
+                base_stats: None,
             },
             Pokemon {
+                id: 12,
                 name: String::from("BlagueSurLesDaron-ne-s"),
-                height: Height(0.7),
-                weight: Weight(9.9),
-                types: vec![Type(String::from("Insecte"))],
-                color: Color(String::from("Vert")),
+                height: Height(7),
+                weight: Weight(99),
+                types: vec![Type::from_name("Insecte")],
+                color: Color::from_name("Vert"),
                 generation: Generation(5),
+                evolution_stage: None,
+                habitat: None,
+                shape: None,
+                status: None,
+                base_stats: None,
             },
             Pokemon {
+                id: 13,
                 name: String::from("Blanche"),
-                height: Height(0.3),
-                weight: Weight(3.2),
-                types: vec![Type(String::from("Normal"))],
-                color: Color(String::from("Blanc")),
+                height: Height(3),
+                weight: Weight(32),
+                types: vec![Type::from_name("Normal")],
+                color: Color::from_name("Blanc"),
                 generation: Generation(2),
+                evolution_stage: None,
+                habitat: None,
+                shape: None,
+                status: None,
+                base_stats: None,
             },
             Pokemon {
+                id: 14,
                 name: String::from("Noirette"),
-                height: Height(0.3),
-                weight: Weight(4.1),
-                types: vec![Type(String::from("Normal"))],
-                color: Color(String::from("Noir")),
+                height: Height(3),
+                weight: Weight(41),
+                types: vec![Type::from_name("Normal")],
+                color: Color::from_name("Noir"),
                 generation: Generation(2),
+                evolution_stage: None,
+                habitat: None,
+                shape: None,
+                status: None,
+                base_stats: None,
             },
         ];
 
-        let names: Vec<String> = pokemons.iter().map(|p| p.name.clone()).collect();
-        (names, pokemons)
+        pokemons
     }
 }
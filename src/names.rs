@@ -0,0 +1,86 @@
+// The localized names of the types and the colors, to find their canonical identifier when the
+// data files only have the name. The identifiers are the ones of PokeAPI.
+
+const TYPE_NAMES: &[(&str, &[&str])] = &[
+    ("normal", &["Normal"]),
+    ("fighting", &["Fighting", "Combat", "Kampf"]),
+    ("flying", &["Flying", "Vol", "Flug"]),
+    ("poison", &["Poison", "Gift"]),
+    ("ground", &["Ground", "Sol", "Boden"]),
+    ("rock", &["Rock", "Roche", "Gestein"]),
+    ("bug", &["Bug", "Insecte", "Käfer"]),
+    ("ghost", &["Ghost", "Spectre", "Geist"]),
+    ("steel", &["Steel", "Acier", "Stahl"]),
+    ("fire", &["Fire", "Feu", "Feuer"]),
+    ("water", &["Water", "Eau", "Wasser"]),
+    ("grass", &["Grass", "Plante", "Pflanze"]),
+    ("electric", &["Electric", "Électrik", "Elektro"]),
+    ("psychic", &["Psychic", "Psy", "Psycho"]),
+    ("ice", &["Ice", "Glace", "Eis"]),
+    ("dragon", &["Dragon", "Drache"]),
+    ("dark", &["Dark", "Ténèbres", "Unlicht"]),
+    ("fairy", &["Fairy", "Fée", "Fee"]),
+];
+
+const COLOR_NAMES: &[(&str, &[&str])] = &[
+    ("black", &["Black", "Noir", "Schwarz"]),
+    ("blue", &["Blue", "Bleu", "Blau"]),
+    ("brown", &["Brown", "Marron", "Braun"]),
+    ("gray", &["Gray", "Gris", "Grau"]),
+    ("green", &["Green", "Vert", "Grün"]),
+    ("pink", &["Pink", "Rose", "Rosa"]),
+    ("purple", &["Purple", "Violet", "Violett", "Lila"]),
+    ("red", &["Red", "Rouge", "Rot"]),
+    ("white", &["White", "Blanc", "Weiß", "Weiss"]),
+    ("yellow", &["Yellow", "Jaune", "Gelb"]),
+];
+
+pub fn get_type_id(name: &str) -> Option<&'static str> {
+    find_id(TYPE_NAMES, name)
+}
+
+pub fn get_color_id(name: &str) -> Option<&'static str> {
+    find_id(COLOR_NAMES, name)
+}
+
+pub fn is_type_id(id: &str) -> bool {
+    TYPE_NAMES.iter().any(|(type_id, _)| *type_id == id)
+}
+
+pub fn is_color_id(id: &str) -> bool {
+    COLOR_NAMES.iter().any(|(color_id, _)| *color_id == id)
+}
+
+// The case is ignored
+fn find_id(table: &[(&'static str, &[&str])], name: &str) -> Option<&'static str> {
+    let name = name.trim().to_lowercase();
+    table
+        .iter()
+        .find(|(_, names)| names.iter().any(|n| n.to_lowercase() == name))
+        .map(|(id, _)| *id)
+}
+
+/*
+    Tests
+*/
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_the_ids() {
+        assert_eq!(get_type_id("Plante"), Some("grass"));
+        assert_eq!(get_type_id("Pflanze"), Some("grass"));
+        assert_eq!(get_type_id("électrik"), Some("electric"));
+        assert_eq!(get_type_id("Sacha"), None);
+
+        assert_eq!(get_color_id("Vert"), Some("green"));
+        assert_eq!(get_color_id("Grün"), Some("green"));
+        assert_eq!(get_color_id("Lila"), Some("purple"));
+        assert_eq!(get_color_id("Turquoise"), None);
+
+        assert!(is_type_id("grass"));
+        assert!(!is_type_id("Plante"));
+        assert!(is_color_id("green"));
+    }
+}
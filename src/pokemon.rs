@@ -1,27 +1,73 @@
 use fs::File;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::ffi::OsStr;
 use std::fs;
+use std::io::BufReader;
+use std::iter::zip;
 use std::path::{Path, PathBuf};
 
-use serde::Deserialize;
+use crate::names::{get_color_id, get_type_id, is_color_id, is_type_id};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use pyo3::prelude::*;
 
+// The path is the file or the directory with the problem, the field is like types[0]
 #[derive(Error, Debug)]
 pub enum ReadingError {
-    #[error("Error while trying to open the file")]
-    SerdeError(#[from] serde_json::Error),
-    #[error("Error while deserializing")]
-    OpeningError(#[from] std::io::Error),
-    #[error("Invalid pokedata structure")]
-    WrongFileStructure,
+    #[error("Error while deserializing {}, field {field}: {source}", .path.display())]
+    SerdeError {
+        path: PathBuf,
+        field: String,
+        source: serde_json::Error,
+    },
+    #[error("Error while trying to open {}: {source}", .path.display())]
+    OpeningError {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Invalid pokedata structure: {}", .0.display())]
+    WrongFileStructure(PathBuf),
+}
+
+#[derive(Error, Debug, PartialEq)]
+#[error("Invalid measure {0}, it must be a positive number")]
+pub struct InvalidMeasure(pub f64);
+
+// The same pokemon must have the same numbers in every language, or the games would differ
+#[derive(Error, Debug, PartialEq)]
+#[error(
+    "The pokemon {id} has the {field} {value} in {lang} but {reference_value} in {reference_lang}"
+)]
+pub struct InconsistentPokemon {
+    pub id: PokemonId,
+    pub field: &'static str,
+    pub lang: Lang,
+    pub value: String,
+    pub reference_lang: Lang,
+    pub reference_value: String,
+}
+
+// A type or a color which is not in the known names, it would only be equal to itself
+#[derive(Error, Debug, PartialEq)]
+#[error("The pokemon {id} has the unknown {field} {name} in {lang}")]
+pub struct UnknownName {
+    pub id: PokemonId,
+    pub field: &'static str,
+    pub lang: Lang,
+    pub name: String,
 }
 
 #[pyclass]
-#[derive(Deserialize, Debug, PartialEq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct Pokemon {
+    // Not in the pokemon files, it comes from the file name (the national dex number), but it is
+    // in the bundle
+    #[pyo3(get)]
+    #[serde(default)]
+    pub id: PokemonId,
     #[pyo3(get)]
     pub name: String,
     #[pyo3(get)]
@@ -34,11 +80,34 @@ pub struct Pokemon {
     pub color: Color,
     #[pyo3(get)]
     pub generation: Generation,
+    // The next attributes are missing in the older data files
+    #[pyo3(get)]
+    #[serde(default)]
+    pub evolution_stage: Option<EvolutionStage>,
+    #[pyo3(get)]
+    #[serde(default)]
+    pub habitat: Option<Habitat>,
+    #[pyo3(get)]
+    #[serde(default)]
+    pub shape: Option<Shape>,
+    #[pyo3(get)]
+    #[serde(default)]
+    pub status: Option<Status>,
+    #[pyo3(get)]
+    #[serde(default)]
+    pub base_stats: Option<BaseStats>,
 }
 
-#[derive(Deserialize, Debug, PartialEq)]
-struct Metadata {
-    names: Vec<String>,
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub(crate) struct Metadata {
+    pub names: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LocalizedName {
+    Name(String),
+    WithId { id: String, name: String },
 }
 
 #[pyclass]
@@ -60,137 +129,247 @@ impl NumberComparison {
     }
 }
 
+// How far the value to guess is from the guess: the value to guess is the guess plus the
+// difference, or the guess times the ratio. There is no ratio when the guess is 0.
 #[pyclass]
 #[derive(Debug, PartialEq, Clone)]
-pub enum TypesComparison {
-    Different,
-    Equal,
-    PartiallyEqual,
+pub struct NumberDistance {
+    #[pyo3(get)]
+    pub difference: f64,
+    #[pyo3(get)]
+    pub ratio: Option<f64>,
+}
+
+impl NumberDistance {
+    pub fn new(guess: f64, value_to_guess: f64) -> NumberDistance {
+        NumberDistance {
+            difference: value_to_guess - guess,
+            ratio: if guess == 0.0 {
+                None
+            } else {
+                Some(value_to_guess / guess)
+            },
+        }
+    }
+
+    // The heights and the weights are subtracted in tenths, so 0.7 - 1 is exactly -0.3
+    pub fn from_tenths(guess: u32, value_to_guess: u32) -> NumberDistance {
+        NumberDistance {
+            difference: (value_to_guess as i64 - guess as i64) as f64 / 10.0,
+            ratio: if guess == 0 {
+                None
+            } else {
+                Some(value_to_guess as f64 / guess as f64)
+            },
+        }
+    }
+}
+
+// The height and the weight are close when the difference is at most this ratio of the value to
+// guess, the generation when the difference is at most this number of generations
+#[pyclass]
+#[derive(Debug, PartialEq, Clone)]
+pub struct ClosenessThresholds {
+    #[pyo3(get, set)]
+    pub height_ratio: f64,
+    #[pyo3(get, set)]
+    pub weight_ratio: f64,
+    #[pyo3(get, set)]
+    pub generation: u8,
 }
 
 #[pymethods]
-impl TypesComparison {
-    fn to_string(&self) -> String {
-        match self {
-            Self::Different => String::from("different"),
-            Self::PartiallyEqual => String::from("partially_equal"),
-            Self::Equal => String::from("equal"),
+impl ClosenessThresholds {
+    #[new]
+    #[pyo3(signature = (height_ratio=0.1, weight_ratio=0.1, generation=1))]
+    pub fn new(height_ratio: f64, weight_ratio: f64, generation: u8) -> Self {
+        ClosenessThresholds {
+            height_ratio,
+            weight_ratio,
+            generation,
         }
     }
 }
 
+impl Default for ClosenessThresholds {
+    fn default() -> Self {
+        ClosenessThresholds::new(0.1, 0.1, 1)
+    }
+}
+
+// Comparison of the type in one slot of the guess, an empty slot is equal to another empty slot
 #[pyclass]
 #[derive(Debug, PartialEq, Clone)]
-pub enum ColorComparison {
-    Different,
+pub enum TypeSlotComparison {
     Equal,
+    // The pokemon to guess has this type, but in the other slot
+    WrongSlot,
+    Different,
+    // The guess has no type in this slot, the pokemon to guess has one
+    Missing,
+    // The guess has a type in this slot, the pokemon to guess has none
+    Unexpected,
 }
 
 #[pymethods]
-impl ColorComparison {
+impl TypeSlotComparison {
     fn to_string(&self) -> String {
         match self {
-            Self::Different => String::from("different"),
             Self::Equal => String::from("equal"),
+            Self::WrongSlot => String::from("wrong_slot"),
+            Self::Different => String::from("different"),
+            Self::Missing => String::from("missing"),
+            Self::Unexpected => String::from("unexpected"),
         }
     }
 }
 
 #[pyclass]
 #[derive(Debug, PartialEq, Clone)]
-pub struct PokemonComparison {
-    #[pyo3(get)]
-    pub success: bool,
-    #[pyo3(get)]
-    pub height: NumberComparison,
+pub struct TypesComparison {
     #[pyo3(get)]
-    pub weight: NumberComparison,
+    pub primary: TypeSlotComparison,
     #[pyo3(get)]
-    pub types: TypesComparison,
-    #[pyo3(get)]
-    pub color: ColorComparison,
-    #[pyo3(get)]
-    pub generation: NumberComparison,
+    pub secondary: TypeSlotComparison,
+}
+
+#[pymethods]
+impl TypesComparison {
+    // Summary of both slots: equal, partially_equal when a type is right or in the wrong slot,
+    // different otherwise
+    fn to_string(&self) -> String {
+        if self.is_equal() {
+            String::from("equal")
+        } else if self.has_common_type() {
+            String::from("partially_equal")
+        } else {
+            String::from("different")
+        }
+    }
 }
 
+impl TypesComparison {
+    pub fn is_equal(&self) -> bool {
+        self.primary == TypeSlotComparison::Equal && self.secondary == TypeSlotComparison::Equal
+    }
 
+    // Even in the wrong slot
+    pub fn has_common_type(&self) -> bool {
+        [&self.primary, &self.secondary].iter().any(|slot| {
+            **slot == TypeSlotComparison::Equal || **slot == TypeSlotComparison::WrongSlot
+        })
+    }
 
-pub fn compare_pokemons(guess: &Pokemon, pokemon_to_guess: &Pokemon) -> PokemonComparison {
-    if guess.name == pokemon_to_guess.name {
-        return PokemonComparison {
-            success: true,
-            height: NumberComparison::Equal,
-            weight: NumberComparison::Equal,
-            types: TypesComparison::Equal,
-            color: ColorComparison::Equal,
-            generation: NumberComparison::Equal,
+    pub fn new(guess: &[Type], pokemon_to_guess: &[Type]) -> TypesComparison {
+        TypesComparison {
+            primary: compare_type_slot(guess, pokemon_to_guess, 0),
+            secondary: compare_type_slot(guess, pokemon_to_guess, 1),
         }
     }
+}
 
-    let height = if guess.height == pokemon_to_guess.height {
-        NumberComparison::Equal
-    } else if guess.height < pokemon_to_guess.height {
-        NumberComparison::Higher
-    } else {
-        NumberComparison::Lower
-    };
+#[pyclass]
+#[derive(Debug, PartialEq, Clone)]
+pub enum ColorComparison {
+    Different,
+    // Not the same color, but from the same family
+    Similar,
+    Equal,
+}
 
-    let weight = if guess.weight == pokemon_to_guess.weight {
-        NumberComparison::Equal
-    } else if guess.weight < pokemon_to_guess.weight {
-        NumberComparison::Higher
-    } else {
-        NumberComparison::Lower
-    };
+#[pymethods]
+impl ColorComparison {
+    fn to_string(&self) -> String {
+        match self {
+            Self::Different => String::from("different"),
+            Self::Similar => String::from("similar"),
+            Self::Equal => String::from("equal"),
+        }
+    }
+}
 
-    let types = if guess.types == pokemon_to_guess.types {
-        TypesComparison::Equal
-    } else {
-        let mut common_type_found = false;
-        for t in guess.types.iter() {
-            if pokemon_to_guess.types.contains(t) {
-                common_type_found = true;
-            }
+// 2 colors are similar if they are in the same family, a color can be in several families.
+// The families are made of color ids, so they work for every language.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ColorFamilies {
+    families: Vec<BTreeSet<String>>,
+}
+
+impl ColorFamilies {
+    pub fn new(families: Vec<Vec<String>>) -> ColorFamilies {
+        ColorFamilies {
+            families: families
+                .into_iter()
+                .map(|family| family.into_iter().collect())
+                .collect(),
         }
+    }
 
-        if common_type_found {
-            TypesComparison::PartiallyEqual
+    pub fn compare(&self, guess: &Color, color_to_guess: &Color) -> ColorComparison {
+        if guess.id == color_to_guess.id {
+            ColorComparison::Equal
+        } else if self
+            .families
+            .iter()
+            .any(|family| family.contains(&guess.id) && family.contains(&color_to_guess.id))
+        {
+            ColorComparison::Similar
         } else {
-            TypesComparison::Different
+            ColorComparison::Different
         }
-    };
+    }
+}
 
-    let color = if guess.color == pokemon_to_guess.color {
-        ColorComparison::Equal
-    } else {
-        ColorComparison::Different
-    };
+impl Default for ColorFamilies {
+    fn default() -> Self {
+        let families = [
+            ["red", "pink"],
+            ["brown", "yellow"],
+            ["blue", "purple"],
+            ["black", "gray"],
+            ["white", "gray"],
+        ];
+        ColorFamilies::new(
+            families
+                .iter()
+                .map(|family| family.iter().map(|color| String::from(*color)).collect())
+                .collect(),
+        )
+    }
+}
 
-    let generation = if guess.generation == pokemon_to_guess.generation {
-        NumberComparison::Equal
-    } else if guess.generation < pokemon_to_guess.generation {
-        NumberComparison::Higher
-    } else {
-        NumberComparison::Lower
-    };
+// Everything the comparison of 2 pokemons can be configured with
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ComparisonConfig {
+    pub closeness_thresholds: ClosenessThresholds,
+    pub color_families: ColorFamilies,
+}
 
-    PokemonComparison {
-        success: false,
-        height,
-        weight,
-        types,
-        color,
-        generation,
-    }
+// The base stats are only compared in the base stats mode, it is harder
+#[pyclass]
+#[derive(Debug, PartialEq, Clone)]
+pub enum GameMode {
+    Classic,
+    BaseStats,
+}
+
+// How the heights and the weights are shown to the player
+#[pyclass]
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum UnitSystem {
+    // Meters and kilograms
+    #[default]
+    Metric,
+    // Feet and inches, and pounds
+    Imperial,
 }
 
 pub fn get_names(mut data_dir: PathBuf) -> Result<BTreeMap<Lang, Vec<String>>, ReadingError> {
     let mut names = BTreeMap::new();
     data_dir.push("generated_data");
-    for dir in std::fs::read_dir(data_dir)? {
-        let dir = dir?;
-        let lang = get_lang(dir.path().file_name())?;
-        let metadata = get_metadata(dir.path().to_path_buf())?;
+    for lang_dir in read_dir(&data_dir)? {
+        let lang = get_lang(&lang_dir)?;
+        let metadata = get_metadata(&lang_dir)?;
         names.insert(lang, metadata.names);
     }
     Ok(names)
@@ -203,18 +382,15 @@ pub fn get_all_pokemons(
 
     data_dir.push("generated_data");
 
-    for lang_dir in std::fs::read_dir(data_dir)? {
-        let lang_dir = lang_dir?;
-        let lang = get_lang(lang_dir.path().file_name())?;
-
-        let mut lang_dir = lang_dir.path().to_path_buf();
-        lang_dir.push("pokedle");
+    for lang_dir in read_dir(&data_dir)? {
+        let lang = get_lang(&lang_dir)?;
 
         let mut pokemons = Vec::new();
-        for poke_file in std::fs::read_dir(lang_dir)? {
-            let poke_file = poke_file?;
-            pokemons.push(read_pokemon(poke_file.path())?);
+        for poke_file in read_dir(&lang_dir.join("pokedle"))? {
+            pokemons.push(read_pokemon(poke_file)?);
         }
+        // The files are sorted by name, 10.json is before 2.json
+        pokemons.sort_by_key(|pokemon| pokemon.id);
 
         pokemons_by_lang.insert(lang, pokemons);
     }
@@ -222,57 +398,267 @@ pub fn get_all_pokemons(
     Ok(pokemons_by_lang)
 }
 
+// Every language is compared to the reference language, a pokemon missing in the reference
+// language is not checked
+pub fn check_consistency(
+    pokemons_by_lang: &BTreeMap<Lang, Vec<Pokemon>>,
+    reference_lang: &str,
+) -> Vec<InconsistentPokemon> {
+    let references: BTreeMap<PokemonId, &Pokemon> = match pokemons_by_lang.get(reference_lang) {
+        Some(pokemons) => pokemons
+            .iter()
+            .map(|pokemon| (pokemon.id, pokemon))
+            .collect(),
+        None => return Vec::new(),
+    };
+    let get_numbers = |pokemon: &Pokemon| -> [(&'static str, String); 4] {
+        [
+            ("height", pokemon.height.to_string()),
+            ("weight", pokemon.weight.to_string()),
+            ("generation", pokemon.generation.to_string()),
+            ("number of types", pokemon.types.len().to_string()),
+        ]
+    };
+
+    let mut inconsistencies = Vec::new();
+    for (lang, pokemons) in pokemons_by_lang {
+        if lang == reference_lang {
+            continue;
+        }
+        for pokemon in pokemons {
+            let reference = match references.get(&pokemon.id) {
+                Some(reference) => reference,
+                None => continue,
+            };
+            let numbers = zip(get_numbers(pokemon), get_numbers(reference));
+            for ((field, value), (_, reference_value)) in numbers {
+                if value != reference_value {
+                    inconsistencies.push(InconsistentPokemon {
+                        id: pokemon.id,
+                        field,
+                        lang: lang.clone(),
+                        value,
+                        reference_lang: String::from(reference_lang),
+                        reference_value,
+                    });
+                }
+            }
+        }
+    }
+    inconsistencies
+}
+
+pub fn check_names(pokemons_by_lang: &BTreeMap<Lang, Vec<Pokemon>>) -> Vec<UnknownName> {
+    let mut unknown_names = Vec::new();
+    for (lang, pokemons) in pokemons_by_lang {
+        for pokemon in pokemons {
+            let types = pokemon
+                .types
+                .iter()
+                .filter(|t| !is_type_id(&t.id))
+                .map(|t| ("type", &t.name));
+            let colors = Some(&pokemon.color)
+                .filter(|color| !is_color_id(&color.id))
+                .map(|color| ("color", &color.name));
+            for (field, name) in types.chain(colors) {
+                unknown_names.push(UnknownName {
+                    id: pokemon.id,
+                    field,
+                    lang: lang.clone(),
+                    name: name.clone(),
+                });
+            }
+        }
+    }
+    unknown_names
+}
+
 /*
     Small types
 */
 pub type Lang = String;
 
+pub type PokemonId = u32;
+
+// The id is the same in every language, like grass, it is the one used to compare.
+// In the json files, it is either only the name, or an object with the id and the name.
+// It is always written with the id.
 #[pyclass]
-#[derive(Deserialize, Debug, PartialEq, Clone)]
-pub struct Type(pub String);
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(from = "LocalizedName")]
+pub struct Type {
+    #[pyo3(get)]
+    pub id: String,
+    #[pyo3(get)]
+    pub name: String,
+}
+
+impl Type {
+    pub fn new(id: &str, name: &str) -> Type {
+        Type {
+            id: String::from(id),
+            name: String::from(name),
+        }
+    }
+
+    // The id is found from the name, for the data files without the id
+    pub fn from_name(name: &str) -> Type {
+        Type {
+            id: get_id_from_name(name, get_type_id),
+            name: String::from(name),
+        }
+    }
+}
 
 #[pymethods]
 impl Type {
     pub fn to_string(&self) -> String {
-        format!("{}", self.0)
+        self.name.clone()
     }
 }
 
+impl From<LocalizedName> for Type {
+    fn from(localized_name: LocalizedName) -> Type {
+        match localized_name {
+            LocalizedName::Name(name) => Type::from_name(&name),
+            LocalizedName::WithId { id, name } => Type { id, name },
+        }
+    }
+}
+
+// Same as Type, the id is like green
 #[pyclass]
-#[derive(Deserialize, Debug, PartialEq, Clone)]
-pub struct Color(pub String);
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(from = "LocalizedName")]
+pub struct Color {
+    #[pyo3(get)]
+    pub id: String,
+    #[pyo3(get)]
+    pub name: String,
+}
+
+impl Color {
+    pub fn new(id: &str, name: &str) -> Color {
+        Color {
+            id: String::from(id),
+            name: String::from(name),
+        }
+    }
+
+    pub fn from_name(name: &str) -> Color {
+        Color {
+            id: get_id_from_name(name, get_color_id),
+            name: String::from(name),
+        }
+    }
+}
 
 #[pymethods]
 impl Color {
     pub fn to_string(&self) -> String {
-        format!("{}", self.0)
+        self.name.clone()
     }
 }
 
+impl From<LocalizedName> for Color {
+    fn from(localized_name: LocalizedName) -> Color {
+        match localized_name {
+            LocalizedName::Name(name) => Color::from_name(&name),
+            LocalizedName::WithId { id, name } => Color { id, name },
+        }
+    }
+}
+
+const POUNDS_PER_KILOGRAM: f64 = 2.204_622_621_848_776;
+const INCHES_PER_METER: f64 = 1.0 / 0.0254;
+
+// In hectograms like in PokeAPI, so the comparison is exact. It is in kilograms in the json files.
 #[pyclass]
-#[derive(Deserialize, Debug, PartialEq, PartialOrd, Clone)]
-pub struct Weight(pub f64);
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(try_from = "f64", into = "f64")]
+pub struct Weight(pub u32);
 
 #[pymethods]
 impl Weight {
+    pub fn get_kilograms(&self) -> f64 {
+        f64::from(self.0) / 10.0
+    }
+
     pub fn to_string(&self) -> String {
-        format!("{}", self.0)
+        format!("{}", self.get_kilograms())
+    }
+
+    // With the unit, like in the pokedex of the games
+    #[pyo3(signature = (units=UnitSystem::Metric))]
+    pub fn format(&self, units: UnitSystem) -> String {
+        match units {
+            UnitSystem::Metric => format!("{} kg", self.get_kilograms()),
+            UnitSystem::Imperial => {
+                format!("{:.1} lbs", self.get_kilograms() * POUNDS_PER_KILOGRAM)
+            }
+        }
+    }
+}
+
+impl From<Weight> for f64 {
+    fn from(weight: Weight) -> f64 {
+        weight.get_kilograms()
+    }
+}
+
+impl TryFrom<f64> for Weight {
+    type Error = InvalidMeasure;
+
+    fn try_from(kilograms: f64) -> Result<Weight, InvalidMeasure> {
+        Ok(Weight(to_tenths(kilograms)?))
     }
 }
 
+// In decimetres like in PokeAPI, it is in meters in the json files
 #[pyclass]
-#[derive(Deserialize, Debug, PartialEq, PartialOrd, Clone)]
-pub struct Height(pub f64);
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[serde(try_from = "f64", into = "f64")]
+pub struct Height(pub u32);
 
 #[pymethods]
 impl Height {
+    pub fn get_meters(&self) -> f64 {
+        f64::from(self.0) / 10.0
+    }
+
     pub fn to_string(&self) -> String {
-        format!("{}", self.0)
+        format!("{}", self.get_meters())
+    }
+
+    // With the unit, like in the pokedex of the games: 0.7 m or 2'04"
+    #[pyo3(signature = (units=UnitSystem::Metric))]
+    pub fn format(&self, units: UnitSystem) -> String {
+        match units {
+            UnitSystem::Metric => format!("{} m", self.get_meters()),
+            UnitSystem::Imperial => {
+                let inches = (self.get_meters() * INCHES_PER_METER).round() as u32;
+                format!("{}'{:02}\"", inches / 12, inches % 12)
+            }
+        }
+    }
+}
+
+impl From<Height> for f64 {
+    fn from(height: Height) -> f64 {
+        height.get_meters()
+    }
+}
+
+impl TryFrom<f64> for Height {
+    type Error = InvalidMeasure;
+
+    fn try_from(meters: f64) -> Result<Height, InvalidMeasure> {
+        Ok(Height(to_tenths(meters)?))
     }
 }
 
 #[pyclass]
-#[derive(Deserialize, Debug, PartialEq, PartialOrd, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, PartialOrd, Clone)]
 pub struct Generation(pub u8);
 
 #[pymethods]
@@ -282,35 +668,201 @@ impl Generation {
     }
 }
 
+// 1 for the pokemons which are not evolved
+#[pyclass]
+#[derive(Serialize, Deserialize, Debug, PartialEq, PartialOrd, Clone)]
+pub struct EvolutionStage(pub u8);
+
+#[pymethods]
+impl EvolutionStage {
+    pub fn to_string(&self) -> String {
+        format!("{}", self.0)
+    }
+}
+
+#[pyclass]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct Habitat(pub String);
+
+#[pymethods]
+impl Habitat {
+    pub fn to_string(&self) -> String {
+        self.0.clone()
+    }
+}
+
+#[pyclass]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct Shape(pub String);
+
+#[pymethods]
+impl Shape {
+    pub fn to_string(&self) -> String {
+        self.0.clone()
+    }
+}
+
+#[pyclass]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Normal,
+    Baby,
+    Legendary,
+    Mythical,
+}
+
+#[pymethods]
+impl Status {
+    pub fn to_string(&self) -> String {
+        match self {
+            Self::Normal => String::from("normal"),
+            Self::Baby => String::from("baby"),
+            Self::Legendary => String::from("legendary"),
+            Self::Mythical => String::from("mythical"),
+        }
+    }
+}
+
+#[pyclass]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct BaseStats {
+    #[pyo3(get)]
+    pub hp: u16,
+    #[pyo3(get)]
+    pub attack: u16,
+    #[pyo3(get)]
+    pub defense: u16,
+    #[pyo3(get)]
+    pub special_attack: u16,
+    #[pyo3(get)]
+    pub special_defense: u16,
+    #[pyo3(get)]
+    pub speed: u16,
+}
+
+#[pymethods]
+impl BaseStats {
+    pub fn get_total(&self) -> u32 {
+        [
+            self.hp,
+            self.attack,
+            self.defense,
+            self.special_attack,
+            self.special_defense,
+            self.speed,
+        ]
+        .into_iter()
+        .map(u32::from)
+        .sum()
+    }
+}
+
 /*
     Private stuff
 */
 
-fn get_lang(filename: Option<&OsStr>) -> Result<Lang, ReadingError> {
-    let lang = match filename {
-        Some(lang) => lang.to_str(),
-        _ => return Err(ReadingError::WrongFileStructure),
+pub(crate) fn get_lang(lang_dir: &Path) -> Result<Lang, ReadingError> {
+    match lang_dir.file_name().and_then(OsStr::to_str) {
+        Some(lang) => Ok(lang.to_string()),
+        None => Err(ReadingError::WrongFileStructure(lang_dir.to_path_buf())),
+    }
+}
+
+pub(crate) fn get_metadata(lang_dir: &Path) -> Result<Metadata, ReadingError> {
+    read_json(&lang_dir.join("metadata.json"))
+}
+
+// The content of a directory, sorted so the order does not depend on the file system
+pub(crate) fn read_dir(dir: &Path) -> Result<Vec<PathBuf>, ReadingError> {
+    let opening_error = |source| ReadingError::OpeningError {
+        path: dir.to_path_buf(),
+        source,
     };
-    let lang = match lang {
-        Some(lang) => lang,
-        _ => return Err(ReadingError::WrongFileStructure),
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir).map_err(opening_error)? {
+        paths.push(entry.map_err(opening_error)?.path());
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+pub(crate) fn read_json<T: DeserializeOwned>(filename: &Path) -> Result<T, ReadingError> {
+    let file = File::open(filename).map_err(|source| ReadingError::OpeningError {
+        path: filename.to_path_buf(),
+        source,
+    })?;
+    let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(file));
+    let serde_error = |field: String, source| ReadingError::SerdeError {
+        path: filename.to_path_buf(),
+        field,
+        source,
     };
-    Ok(lang.to_string())
+    let value = serde_path_to_error::deserialize(&mut deserializer)
+        .map_err(|err| serde_error(err.path().to_string(), err.into_inner()))?;
+    // Nothing after the json value
+    deserializer
+        .end()
+        .map_err(|err| serde_error(String::from("."), err))?;
+    Ok(value)
+}
+
+// Higher means the value to guess is higher than the guess
+pub(crate) fn compare_numbers<T: PartialOrd>(guess: &T, value_to_guess: &T) -> NumberComparison {
+    if guess == value_to_guess {
+        NumberComparison::Equal
+    } else if guess < value_to_guess {
+        NumberComparison::Higher
+    } else {
+        NumberComparison::Lower
+    }
+}
+
+fn compare_type_slot(guess: &[Type], pokemon_to_guess: &[Type], slot: usize) -> TypeSlotComparison {
+    match (guess.get(slot), pokemon_to_guess.get(slot)) {
+        (None, None) => TypeSlotComparison::Equal,
+        (None, Some(_)) => TypeSlotComparison::Missing,
+        (Some(t), Some(expected)) if t.id == expected.id => TypeSlotComparison::Equal,
+        (Some(t), _) if pokemon_to_guess.iter().any(|other| other.id == t.id) => {
+            TypeSlotComparison::WrongSlot
+        }
+        (Some(_), None) => TypeSlotComparison::Unexpected,
+        (Some(_), Some(_)) => TypeSlotComparison::Different,
+    }
 }
 
-fn get_metadata(mut lang_dir: PathBuf) -> Result<Metadata, ReadingError> {
-    lang_dir.push("metadata.json");
-    let file = File::open(lang_dir)?;
-    let metadata: Metadata = serde_json::from_reader(file)?;
-    Ok(metadata)
+// The data files have the height and the weight with one decimal
+fn to_tenths(value: f64) -> Result<u32, InvalidMeasure> {
+    let tenths = (value * 10.0).round();
+    if tenths.is_nan() || tenths < 0.0 || tenths > f64::from(u32::MAX) {
+        return Err(InvalidMeasure(value));
+    }
+    Ok(tenths as u32)
 }
 
-fn read_pokemon<P>(filename: P) -> Result<Pokemon, ReadingError>
+// An unknown name is its own id, so it is still equal to itself, check_names reports it
+fn get_id_from_name(name: &str, get_id: fn(&str) -> Option<&'static str>) -> String {
+    match get_id(name) {
+        Some(id) => String::from(id),
+        None => name.trim().to_lowercase(),
+    }
+}
+
+// The name of the file is the national dex number
+pub(crate) fn get_pokemon_id(filename: &Path) -> Result<PokemonId, ReadingError> {
+    match filename.file_stem().and_then(OsStr::to_str).map(str::parse) {
+        Some(Ok(id)) => Ok(id),
+        _ => Err(ReadingError::WrongFileStructure(filename.to_path_buf())),
+    }
+}
+
+pub(crate) fn read_pokemon<P>(filename: P) -> Result<Pokemon, ReadingError>
 where
     P: AsRef<Path>,
 {
-    let file = File::open(filename)?;
-    let pokemon: Pokemon = serde_json::from_reader(file)?;
+    let id = get_pokemon_id(filename.as_ref())?;
+    let mut pokemon: Pokemon = read_json(filename.as_ref())?;
+    pokemon.id = id;
     Ok(pokemon)
 }
 
@@ -324,12 +876,18 @@ mod tests {
     #[test]
     fn read_a_pokemon() {
         let chrysacier = Pokemon {
+            id: 11,
             name: String::from("Chrysacier"),
-            height: Height(0.7),
-            weight: Weight(9.9),
-            types: vec![Type(String::from("Insecte"))],
-            color: Color(String::from("Vert")),
+            height: Height(7),
+            weight: Weight(99),
+            types: vec![Type::from_name("Insecte")],
+            color: Color::from_name("Vert"),
             generation: Generation(1),
+            evolution_stage: None,
+            habitat: None,
+            shape: None,
+            status: None,
+            base_stats: None,
         };
 
         let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -357,133 +915,311 @@ mod tests {
     }
 
     #[test]
-    fn test_compare_same() {
-        // This is not a real use case, but it allows to test equality on all fields
-        let chrysacier = Pokemon {
-            name: String::from("Chrysacier"),
-            height: Height(0.7),
-            weight: Weight(9.9),
-            types: vec![Type(String::from("Insecte"))],
-            color: Color(String::from("Vert")),
-            generation: Generation(1),
-        };
-
-        let chrysacier_bis = Pokemon {
-            name: String::from("Chrysacier"),
-            height: Height(0.7),
-            weight: Weight(9.9),
-            types: vec![Type(String::from("Insecte"))],
-            color: Color(String::from("Vert")),
-            generation: Generation(1),
-        };
+    fn reading_errors() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data/generated_data/fr/pokedle/404.json");
+        let err = read_pokemon(&d).unwrap_err();
+        assert!(format!("{}", err).starts_with("Error while trying to open"));
+        assert!(format!("{}", err).contains("404.json"));
 
-        let good_guess: PokemonComparison = PokemonComparison {
-            success: true,
-            height: NumberComparison::Equal,
-            weight: NumberComparison::Equal,
-            types: TypesComparison::Equal,
-            color: ColorComparison::Equal,
-            generation: NumberComparison::Equal,
-        };
+        // Not a pokemon
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data/generated_data/fr/metadata.json");
+        match read_json::<Pokemon>(&d).unwrap_err() {
+            ReadingError::SerdeError { path, field, .. } => {
+                assert_eq!(path, d);
+                assert_eq!(field, ".");
+            }
+            err => panic!("Unexpected error {}", err),
+        }
+    }
 
-        assert_eq!(compare_pokemons(&chrysacier, &chrysacier_bis), good_guess);
+    #[test]
+    fn check_the_consistency() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data");
+        let mut pokemons = get_all_pokemons(d).unwrap();
+        assert_eq!(check_consistency(&pokemons, "fr"), vec![]);
+
+        let raupy = &mut pokemons.get_mut("de").unwrap()[9];
+        raupy.weight = Weight(30);
+        raupy.types.push(Type::from_name("Gift"));
+        assert_eq!(
+            check_consistency(&pokemons, "fr"),
+            vec![
+                InconsistentPokemon {
+                    id: 10,
+                    field: "weight",
+                    lang: String::from("de"),
+                    value: String::from("3"),
+                    reference_lang: String::from("fr"),
+                    reference_value: String::from("2.9"),
+                },
+                InconsistentPokemon {
+                    id: 10,
+                    field: "number of types",
+                    lang: String::from("de"),
+                    value: String::from("2"),
+                    reference_lang: String::from("fr"),
+                    reference_value: String::from("1"),
+                },
+            ]
+        );
+        assert_eq!(
+            format!("{}", check_consistency(&pokemons, "fr")[0]),
+            "The pokemon 10 has the weight 3 in de but 2.9 in fr"
+        );
+        // The other way around
+        assert_eq!(check_consistency(&pokemons, "de")[0].lang, "fr");
     }
 
     #[test]
-    fn test_compare_partial_equal_type_lower_numbers() {
-        // This is not a real use case, but it allows to test equality on all fields
-        let chrysacier = Pokemon {
-            name: String::from("Chrysacier"),
-            height: Height(0.7),
-            weight: Weight(9.9),
-            types: vec![Type(String::from("Insecte"))],
-            color: Color(String::from("Vert")),
-            generation: Generation(1),
-        };
+    fn check_the_names() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data");
+        let mut pokemons = get_all_pokemons(d).unwrap();
+        assert_eq!(check_names(&pokemons), vec![]);
+
+        let chenipan = &mut pokemons.get_mut("fr").unwrap()[9];
+        chenipan.types = vec![Type::from_name("Plnate")];
+        chenipan.color = Color::from_name("Turquoise");
+        assert_eq!(
+            check_names(&pokemons),
+            vec![
+                UnknownName {
+                    id: 10,
+                    field: "type",
+                    lang: String::from("fr"),
+                    name: String::from("Plnate"),
+                },
+                UnknownName {
+                    id: 10,
+                    field: "color",
+                    lang: String::from("fr"),
+                    name: String::from("Turquoise"),
+                },
+            ]
+        );
+        assert_eq!(
+            format!("{}", check_names(&pokemons)[0]),
+            "The pokemon 10 has the unknown type Plnate in fr"
+        );
+    }
 
-        let my_creature = Pokemon {
-            name: String::from("my_creature"),
-            height: Height(0.8),
-            weight: Weight(10.0),
-            types: vec![Type(String::from("Insecte")), Type(String::from("Feu"))],
-            color: Color(String::from("Rouge")),
-            generation: Generation(2),
-        };
+    #[test]
+    fn get_all_pokemons_sorted_by_id() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data");
+        let pokemons = get_all_pokemons(d).unwrap();
+        for pokemons in pokemons.values() {
+            let ids: Vec<PokemonId> = pokemons.iter().map(|pokemon| pokemon.id).collect();
+            assert_eq!(ids, (1..=10).collect::<Vec<PokemonId>>());
+        }
+        assert_eq!(pokemons["fr"][9].name, "Chenipan");
+    }
 
-        let expected_result = PokemonComparison {
-            success: false,
-            height: NumberComparison::Lower,
-            weight: NumberComparison::Lower,
-            types: TypesComparison::PartiallyEqual,
-            color: ColorComparison::Different,
-            generation: NumberComparison::Lower,
-        };
+    #[test]
+    fn read_an_old_pokemon() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data/generated_data/fr/pokedle/1.json");
+        let pokemon = read_pokemon(d).unwrap();
+        assert_eq!(pokemon.name, "Bulbizarre");
+        assert_eq!(pokemon.evolution_stage, None);
+        assert_eq!(pokemon.habitat, None);
+        assert_eq!(pokemon.shape, None);
+        assert_eq!(pokemon.status, None);
+    }
 
-        assert_eq!(compare_pokemons(&my_creature, &chrysacier), expected_result);
+    #[test]
+    fn deserialize_the_optional_attributes() {
+        let pokemon: Pokemon = serde_json::from_str(
+            r#"{
+                "color": "Jaune",
+                "generation": 2,
+                "height": 0.3,
+                "name": "Pichu",
+                "types": ["Électrik"],
+                "weight": 2.0,
+                "evolution_stage": 1,
+                "habitat": "Forêt",
+                "shape": "Quadrupède",
+                "status": "baby"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(pokemon.evolution_stage, Some(EvolutionStage(1)));
+        assert_eq!(pokemon.habitat, Some(Habitat(String::from("Forêt"))));
+        assert_eq!(pokemon.shape, Some(Shape(String::from("Quadrupède"))));
+        assert_eq!(pokemon.status, Some(Status::Baby));
     }
 
     #[test]
-    fn test_compare_partial_equal_reverse() {
-        // This is not a real use case, but it allows to test equality on all fields
-        let chrysacier = Pokemon {
-            name: String::from("Chrysacier"),
-            height: Height(0.7),
-            weight: Weight(9.9),
-            types: vec![Type(String::from("Insecte"))],
-            color: Color(String::from("Vert")),
-            generation: Generation(1),
-        };
+    fn test_compare_colors() {
+        let color = |name| Color::from_name(name);
+        let families = ColorFamilies::default();
+        assert_eq!(
+            families.compare(&color("Rouge"), &color("Rouge")),
+            ColorComparison::Equal
+        );
+        assert_eq!(
+            families.compare(&color("Rouge"), &color("Rose")),
+            ColorComparison::Similar
+        );
+        assert_eq!(
+            families.compare(&color("Gelb"), &color("Braun")),
+            ColorComparison::Similar
+        );
+        assert_eq!(
+            families.compare(&color("Rouge"), &color("Vert")),
+            ColorComparison::Different
+        );
+
+        // Gris is in 2 families, but Noir and Blanc are not similar
+        assert_eq!(
+            families.compare(&color("Gris"), &color("Noir")),
+            ColorComparison::Similar
+        );
+        assert_eq!(
+            families.compare(&color("Gris"), &color("Blanc")),
+            ColorComparison::Similar
+        );
+        assert_eq!(
+            families.compare(&color("Noir"), &color("Blanc")),
+            ColorComparison::Different
+        );
+
+        let families = ColorFamilies::new(vec![vec![String::from("red"), String::from("green")]]);
+        assert_eq!(
+            families.compare(&color("Rouge"), &color("Vert")),
+            ColorComparison::Similar
+        );
+        assert_eq!(
+            families.compare(&color("Rouge"), &color("Rose")),
+            ColorComparison::Different
+        );
+    }
 
-        let my_creature = Pokemon {
-            name: String::from("my_creature"),
-            height: Height(0.8),
-            weight: Weight(10.0),
-            types: vec![Type(String::from("Insecte")), Type(String::from("Feu"))],
-            color: Color(String::from("Rouge")),
-            generation: Generation(2),
-        };
+    #[test]
+    fn deserialize_the_types_and_the_colors() {
+        // Only the name, the id is found from it
+        let t: Type = serde_json::from_str(r#""Pflanze""#).unwrap();
+        assert_eq!(t, Type::new("grass", "Pflanze"));
+        let c: Color = serde_json::from_str(r#""Grün""#).unwrap();
+        assert_eq!(c, Color::new("green", "Grün"));
+
+        // With the id, the name is only displayed
+        let t: Type = serde_json::from_str(r#"{"id": "grass", "name": "Plnate"}"#).unwrap();
+        assert_eq!(t, Type::new("grass", "Plnate"));
+        let c: Color = serde_json::from_str(r#"{"id": "green", "name": "Vret"}"#).unwrap();
+        assert_eq!(c, Color::new("green", "Vret"));
+
+        // An unknown name is its own id
+        assert_eq!(Type::from_name("Cosmique").id, "cosmique");
+    }
 
-        let expected_result = PokemonComparison {
-            success: false,
-            height: NumberComparison::Higher,
-            weight: NumberComparison::Higher,
-            types: TypesComparison::PartiallyEqual,
-            color: ColorComparison::Different,
-            generation: NumberComparison::Higher,
-        };
+    #[test]
+    fn deserialize_the_measures() {
+        let height: Height = serde_json::from_str("0.7").unwrap();
+        assert_eq!(height, Height(7));
+        assert_eq!(height.to_string(), "0.7");
+        let weight: Weight = serde_json::from_str("6.9").unwrap();
+        assert_eq!(weight, Weight(69));
+        assert_eq!(weight.to_string(), "6.9");
+
+        // A regenerated dataset can have rounding errors, it is still the same value
+        let other_height: Height = serde_json::from_str("0.70000001").unwrap();
+        assert_eq!(
+            compare_numbers(&height, &other_height),
+            NumberComparison::Equal
+        );
+
+        serde_json::from_str::<Height>("-0.7").expect_err("A height can't be negative");
+        serde_json::from_str::<Weight>("-6.9").expect_err("A weight can't be negative");
+        Height::try_from(f64::NAN).expect_err("NaN is not a height");
+        Weight::try_from(f64::INFINITY).expect_err("The weight is too big");
+    }
 
-        assert_eq!(compare_pokemons(&chrysacier, &my_creature), expected_result);
+    #[test]
+    fn format_the_measures() {
+        // Bulbizarre
+        assert_eq!(Height(7).format(UnitSystem::Metric), "0.7 m");
+        assert_eq!(Height(7).format(UnitSystem::Imperial), "2'04\"");
+        assert_eq!(Weight(69).format(UnitSystem::Metric), "6.9 kg");
+        assert_eq!(Weight(69).format(UnitSystem::Imperial), "15.2 lbs");
+
+        // Steelix
+        assert_eq!(Height(92).format(UnitSystem::Imperial), "30'02\"");
+        assert_eq!(Weight(4000).format(UnitSystem::Imperial), "881.8 lbs");
     }
 
     #[test]
-    fn test_compare_totally_different() {
-        // This is not a real use case, but it allows to test equality on all fields
-        let chrysacier = Pokemon {
-            name: String::from("Chrysacier"),
-            height: Height(0.7),
-            weight: Weight(9.9),
-            types: vec![Type(String::from("Insecte"))],
-            color: Color(String::from("Vert")),
-            generation: Generation(1),
+    fn test_compare_types_by_slot() {
+        let types = |names: &[&str]| -> Vec<Type> {
+            names.iter().map(|name| Type::from_name(name)).collect()
         };
 
-        let my_creature = Pokemon {
-            name: String::from("my_creature"),
-            height: Height(0.8),
-            weight: Weight(10.0),
-            types: vec![Type(String::from("Acier")), Type(String::from("Feu"))],
-            color: Color(String::from("Rouge")),
-            generation: Generation(2),
-        };
+        let comparison =
+            TypesComparison::new(&types(&["Plante", "Poison"]), &types(&["Poison", "Plante"]));
+        assert_eq!(
+            comparison,
+            TypesComparison {
+                primary: TypeSlotComparison::WrongSlot,
+                secondary: TypeSlotComparison::WrongSlot,
+            }
+        );
+        assert_eq!(comparison.to_string(), "partially_equal");
+
+        let comparison = TypesComparison::new(&types(&["Feu"]), &types(&["Plante", "Feu"]));
+        assert_eq!(
+            comparison,
+            TypesComparison {
+                primary: TypeSlotComparison::WrongSlot,
+                secondary: TypeSlotComparison::Missing,
+            }
+        );
+
+        let comparison = TypesComparison::new(&types(&["Plante", "Feu"]), &types(&["Poison"]));
+        assert_eq!(
+            comparison,
+            TypesComparison {
+                primary: TypeSlotComparison::Different,
+                secondary: TypeSlotComparison::Unexpected,
+            }
+        );
+        assert_eq!(comparison.to_string(), "different");
+
+        let comparison = TypesComparison::new(&types(&["Feu"]), &types(&["Feu"]));
+        assert_eq!(
+            comparison,
+            TypesComparison {
+                primary: TypeSlotComparison::Equal,
+                secondary: TypeSlotComparison::Equal,
+            }
+        );
+        assert_eq!(comparison.to_string(), "equal");
+    }
 
-        let expected_result = PokemonComparison {
-            success: false,
-            height: NumberComparison::Higher,
-            weight: NumberComparison::Higher,
-            types: TypesComparison::Different,
-            color: ColorComparison::Different,
-            generation: NumberComparison::Higher,
-        };
-        assert_eq!(compare_pokemons(&chrysacier, &my_creature), expected_result);
+    #[test]
+    fn test_number_distance() {
+        let distance = NumberDistance::new(0.5, 2.0);
+        assert_eq!(distance.difference, 1.5);
+        assert_eq!(distance.ratio, Some(4.0));
+
+        let distance = NumberDistance::new(4.0, 1.0);
+        assert_eq!(distance.difference, -3.0);
+        assert_eq!(distance.ratio, Some(0.25));
+
+        let distance = NumberDistance::new(0.0, 1.0);
+        assert_eq!(distance.difference, 1.0);
+        assert_eq!(distance.ratio, None);
+
+        // Not -0.30000000000000004 like in metres
+        let distance = NumberDistance::from_tenths(10, 7);
+        assert_eq!(distance.difference, -0.3);
+        assert_eq!(distance.ratio, Some(0.7));
+
+        let distance = NumberDistance::from_tenths(0, 7);
+        assert_eq!(distance.difference, 0.7);
+        assert_eq!(distance.ratio, None);
     }
 }
@@ -0,0 +1,48 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Map, Value};
+
+// The json of a Bundle (src/bundle.rs) made with serde_json only, so build.rs can use it. The ids
+// come from the file names and the types and the colors get their ids when the bundle is read
+pub fn create_raw_bundle(pokle_dir: &Path) -> Value {
+    let mut languages = Map::new();
+    for lang_dir in read_dir(&pokle_dir.join("generated_data")) {
+        let lang = lang_dir.file_name().unwrap().to_str().unwrap().to_string();
+        let metadata = read_json(&lang_dir.join("metadata.json"));
+
+        let mut pokemons = Vec::new();
+        for pokemon_file in read_dir(&lang_dir.join("pokedle")) {
+            let id: u32 = match pokemon_file.file_stem().and_then(|stem| stem.to_str()) {
+                Some(stem) => stem.parse().unwrap_or_else(|_| {
+                    panic!("Invalid pokemon file name {}", pokemon_file.display())
+                }),
+                None => panic!("Invalid pokemon file name {}", pokemon_file.display()),
+            };
+            let mut pokemon = read_json(&pokemon_file);
+            pokemon["id"] = json!(id);
+            pokemons.push(pokemon);
+        }
+
+        languages.insert(
+            lang,
+            json!({"names": metadata["names"], "pokemons": pokemons}),
+        );
+    }
+    json!({ "languages": languages })
+}
+
+fn read_dir(dir: &Path) -> Vec<PathBuf> {
+    let entries = fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("Error while reading {}: {}", dir.display(), err));
+    let mut paths: Vec<PathBuf> = entries.map(|entry| entry.unwrap().path()).collect();
+    paths.sort();
+    paths
+}
+
+fn read_json(path: &Path) -> Value {
+    let content = fs::read_to_string(path)
+        .unwrap_or_else(|err| panic!("Error while reading {}: {}", path.display(), err));
+    serde_json::from_str(&content)
+        .unwrap_or_else(|err| panic!("Error while deserializing {}: {}", path.display(), err))
+}
@@ -0,0 +1,159 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::PokemonId;
+
+#[derive(Error, Debug)]
+pub enum StateError {
+    #[error("Error while reading or writing the state file: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Invalid state file: {0}")]
+    SerdeError(#[from] serde_json::Error),
+}
+
+// What must survive a restart of the server
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct DailyState {
+    pub seed: u64,
+    // The pokemon of each puzzle already played
+    pub history: BTreeMap<NaiveDate, PokemonId>,
+}
+
+// No state file yet is not an error, it is the first start
+pub fn read_state<P>(filename: P) -> Result<Option<DailyState>, StateError>
+where
+    P: AsRef<Path>,
+{
+    let file = match File::open(filename) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let state: DailyState = serde_json::from_reader(file)?;
+    Ok(Some(state))
+}
+
+pub fn write_state<P>(filename: P, state: &DailyState) -> Result<(), StateError>
+where
+    P: AsRef<Path>,
+{
+    write_json(filename, state)
+}
+
+// The pokemons chosen by hand for some dates, no file means no override
+pub fn read_overrides<P>(filename: P) -> Result<BTreeMap<NaiveDate, PokemonId>, StateError>
+where
+    P: AsRef<Path>,
+{
+    let file = match File::open(filename) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(err) => return Err(err.into()),
+    };
+    let overrides = serde_json::from_reader(file)?;
+    Ok(overrides)
+}
+
+pub fn write_overrides<P>(
+    filename: P,
+    overrides: &BTreeMap<NaiveDate, PokemonId>,
+) -> Result<(), StateError>
+where
+    P: AsRef<Path>,
+{
+    write_json(filename, overrides)
+}
+
+// The file is written in another file then renamed, so a crash while writing can't corrupt it
+fn write_json<P, T>(filename: P, value: &T) -> Result<(), StateError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    let mut tmp_filename = filename.as_ref().as_os_str().to_owned();
+    tmp_filename.push(".tmp");
+
+    let mut file = File::create(&tmp_filename)?;
+    serde_json::to_writer_pretty(&file, value)?;
+    file.flush()?;
+    file.sync_all()?;
+    fs::rename(&tmp_filename, filename)?;
+    Ok(())
+}
+
+/*
+    Tests
+*/
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn write_and_read_state() {
+        let mut filename = std::env::temp_dir();
+        filename.push("pokedle_write_and_read_state.json");
+
+        let state = DailyState {
+            seed: 42,
+            history: BTreeMap::from([
+                (NaiveDate::from_ymd_opt(2023, 10, 13).unwrap(), 12),
+                (NaiveDate::from_ymd_opt(2023, 10, 14).unwrap(), 4),
+            ]),
+        };
+        write_state(&filename, &state).unwrap();
+        assert_eq!(read_state(&filename).unwrap(), Some(state.clone()));
+
+        // Writing again replaces the whole file
+        let state = DailyState {
+            seed: 43,
+            history: BTreeMap::new(),
+        };
+        write_state(&filename, &state).unwrap();
+        assert_eq!(read_state(&filename).unwrap(), Some(state));
+
+        fs::remove_file(filename).unwrap();
+    }
+
+    #[test]
+    fn read_missing_state() {
+        let mut filename = std::env::temp_dir();
+        filename.push("pokedle_read_missing_state.json");
+        assert_eq!(read_state(filename).unwrap(), None);
+    }
+
+    #[test]
+    fn write_and_read_overrides() {
+        let mut filename = std::env::temp_dir();
+        filename.push("pokedle_write_and_read_overrides.json");
+        let _ = fs::remove_file(&filename);
+        assert_eq!(read_overrides(&filename).unwrap(), BTreeMap::new());
+
+        let overrides = BTreeMap::from([
+            (NaiveDate::from_ymd_opt(2023, 10, 31).unwrap(), 94),
+            (NaiveDate::from_ymd_opt(2023, 11, 18).unwrap(), 906),
+        ]);
+        write_overrides(&filename, &overrides).unwrap();
+        assert_eq!(read_overrides(&filename).unwrap(), overrides);
+
+        // The dates are the keys, it is easy to edit by hand
+        let content = fs::read_to_string(&filename).unwrap();
+        assert!(content.contains("\"2023-10-31\": 94"));
+
+        fs::remove_file(filename).unwrap();
+    }
+
+    #[test]
+    fn read_invalid_state() {
+        let mut filename = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        filename.push("small_test_data/generated_data/fr/metadata.json");
+        read_state(filename).expect_err("This is not a state file");
+    }
+}
@@ -0,0 +1,473 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use pyo3::prelude::*;
+
+use crate::names::{is_color_id, is_type_id};
+use crate::pokemon::{
+    check_consistency, get_lang, get_metadata, read_dir, read_pokemon, ReadingError,
+};
+use crate::{Lang, Pokemon, PokemonId};
+
+const MAX_GENERATION: u8 = 9;
+const MAX_EVOLUTION_STAGE: u8 = 3;
+const MAX_BASE_STAT: u16 = 255;
+
+// A problem in the data, the field is None when the problem is about the whole file
+#[pyclass]
+#[derive(Debug, PartialEq, Clone)]
+pub struct DataIssue {
+    #[pyo3(get)]
+    pub path: PathBuf,
+    #[pyo3(get)]
+    pub field: Option<String>,
+    #[pyo3(get)]
+    pub message: String,
+}
+
+impl DataIssue {
+    fn new(path: &Path, field: Option<&str>, message: String) -> DataIssue {
+        DataIssue {
+            path: path.to_path_buf(),
+            field: field.map(String::from),
+            message,
+        }
+    }
+}
+
+#[pymethods]
+impl DataIssue {
+    pub fn to_string(&self) -> String {
+        match &self.field {
+            Some(field) => format!("{}, {}: {}", self.path.display(), field, self.message),
+            None => format!("{}: {}", self.path.display(), self.message),
+        }
+    }
+}
+
+impl From<ReadingError> for DataIssue {
+    fn from(err: ReadingError) -> DataIssue {
+        match err {
+            ReadingError::SerdeError {
+                path,
+                field,
+                source,
+            } => DataIssue {
+                path,
+                field: Some(field),
+                message: source.to_string(),
+            },
+            ReadingError::OpeningError { path, source } => DataIssue {
+                path,
+                field: None,
+                message: source.to_string(),
+            },
+            ReadingError::WrongFileStructure(path) => DataIssue {
+                path,
+                field: None,
+                message: String::from("Invalid pokedata structure"),
+            },
+        }
+    }
+}
+
+// Checks the whole generated_data directory, unlike the loading it does not stop at the first
+// problem. The languages are the ones which must be there, an empty list means any language.
+// The first one is the reference for the cross-language checks.
+#[pyfunction]
+#[pyo3(signature = (pokle_dir, langs=Vec::new()))]
+pub fn validate_data(pokle_dir: PathBuf, langs: Vec<Lang>) -> Vec<DataIssue> {
+    let data_dir = pokle_dir.join("generated_data");
+    let lang_dirs = match read_dir(&data_dir) {
+        Ok(lang_dirs) => lang_dirs,
+        Err(err) => return vec![err.into()],
+    };
+
+    let mut issues = Vec::new();
+    let mut pokemons_by_lang = BTreeMap::new();
+    for lang_dir in lang_dirs {
+        match get_lang(&lang_dir) {
+            Ok(lang) => {
+                let pokemons = validate_lang(&lang_dir, &mut issues);
+                pokemons_by_lang.insert(lang, pokemons);
+            }
+            Err(err) => issues.push(err.into()),
+        }
+    }
+
+    for lang in &langs {
+        if !pokemons_by_lang.contains_key(lang) {
+            issues.push(DataIssue::new(
+                &data_dir.join(lang),
+                None,
+                format!("The language {} is missing", lang),
+            ));
+        }
+    }
+    validate_same_pokemons(&data_dir, &pokemons_by_lang, &mut issues);
+
+    let reference_lang = langs
+        .first()
+        .or(pokemons_by_lang.keys().next())
+        .cloned()
+        .unwrap_or_default();
+    let pokemons_by_lang: BTreeMap<Lang, Vec<Pokemon>> = pokemons_by_lang
+        .into_iter()
+        .map(|(lang, pokemons)| (lang, pokemons.into_values().collect()))
+        .collect();
+    for inconsistency in check_consistency(&pokemons_by_lang, &reference_lang) {
+        issues.push(DataIssue::new(
+            &data_dir
+                .join(&inconsistency.lang)
+                .join("pokedle")
+                .join(format!("{}.json", inconsistency.id)),
+            Some(inconsistency.field),
+            format!("{}", inconsistency),
+        ));
+    }
+    issues
+}
+
+fn validate_lang(lang_dir: &Path, issues: &mut Vec<DataIssue>) -> BTreeMap<PokemonId, Pokemon> {
+    let pokemons = read_pokemons(&lang_dir.join("pokedle"), issues);
+    match get_metadata(lang_dir) {
+        Ok(metadata) => validate_names(
+            &lang_dir.join("metadata.json"),
+            &metadata.names,
+            &pokemons,
+            issues,
+        ),
+        Err(err) => issues.push(err.into()),
+    }
+    pokemons
+}
+
+fn read_pokemons(pokedle_dir: &Path, issues: &mut Vec<DataIssue>) -> BTreeMap<PokemonId, Pokemon> {
+    let mut pokemons = BTreeMap::new();
+    let files = match read_dir(pokedle_dir) {
+        Ok(files) => files,
+        Err(err) => {
+            issues.push(err.into());
+            return pokemons;
+        }
+    };
+
+    let mut files_by_name: BTreeMap<String, PathBuf> = BTreeMap::new();
+    for file in files {
+        let pokemon = match read_pokemon(&file) {
+            Ok(pokemon) => pokemon,
+            Err(err) => {
+                issues.push(err.into());
+                continue;
+            }
+        };
+        validate_pokemon(&file, &pokemon, issues);
+
+        // 1.json and 01.json have the same id
+        if pokemons.contains_key(&pokemon.id) {
+            issues.push(DataIssue::new(
+                &file,
+                None,
+                format!("The id {} is in several files", pokemon.id),
+            ));
+            continue;
+        }
+        match files_by_name.get(&pokemon.name) {
+            Some(other_file) => issues.push(DataIssue::new(
+                &file,
+                Some("name"),
+                format!(
+                    "The name {} is also the one of {}",
+                    pokemon.name,
+                    other_file.display()
+                ),
+            )),
+            None => {
+                files_by_name.insert(pokemon.name.clone(), file.clone());
+            }
+        }
+        pokemons.insert(pokemon.id, pokemon);
+    }
+    pokemons
+}
+
+fn validate_pokemon(file: &Path, pokemon: &Pokemon, issues: &mut Vec<DataIssue>) {
+    let mut add_issue = |field: &str, message: String| {
+        issues.push(DataIssue::new(file, Some(field), message));
+    };
+
+    if pokemon.name.trim().is_empty() {
+        add_issue("name", String::from("The name is empty"));
+    }
+    if pokemon.height.0 == 0 {
+        add_issue("height", String::from("The height must be more than 0"));
+    }
+    if pokemon.weight.0 == 0 {
+        add_issue("weight", String::from("The weight must be more than 0"));
+    }
+    if pokemon.types.is_empty() || pokemon.types.len() > 2 {
+        add_issue(
+            "types",
+            format!("There must be 1 or 2 types, not {}", pokemon.types.len()),
+        );
+    }
+    for (i, t) in pokemon.types.iter().enumerate() {
+        if !is_type_id(&t.id) {
+            add_issue(&format!("types[{}]", i), format!("Unknown type {}", t.name));
+        }
+    }
+    if !is_color_id(&pokemon.color.id) {
+        add_issue("color", format!("Unknown color {}", pokemon.color.name));
+    }
+    if !(1..=MAX_GENERATION).contains(&pokemon.generation.0) {
+        add_issue(
+            "generation",
+            format!(
+                "The generation must be between 1 and {}, not {}",
+                MAX_GENERATION, pokemon.generation.0
+            ),
+        );
+    }
+    if let Some(stage) = &pokemon.evolution_stage {
+        if !(1..=MAX_EVOLUTION_STAGE).contains(&stage.0) {
+            add_issue(
+                "evolution_stage",
+                format!(
+                    "The evolution stage must be between 1 and {}, not {}",
+                    MAX_EVOLUTION_STAGE, stage.0
+                ),
+            );
+        }
+    }
+    if let Some(stats) = &pokemon.base_stats {
+        let all_stats = [
+            ("hp", stats.hp),
+            ("attack", stats.attack),
+            ("defense", stats.defense),
+            ("special_attack", stats.special_attack),
+            ("special_defense", stats.special_defense),
+            ("speed", stats.speed),
+        ];
+        for (name, value) in all_stats {
+            if !(1..=MAX_BASE_STAT).contains(&value) {
+                add_issue(
+                    &format!("base_stats.{}", name),
+                    format!(
+                        "A base stat must be between 1 and {}, not {}",
+                        MAX_BASE_STAT, value
+                    ),
+                );
+            }
+        }
+    }
+}
+
+// The names of metadata.json are the ones of the pokemon files
+fn validate_names(
+    metadata_file: &Path,
+    names: &[String],
+    pokemons: &BTreeMap<PokemonId, Pokemon>,
+    issues: &mut Vec<DataIssue>,
+) {
+    let pokemon_names: BTreeSet<&str> = pokemons.values().map(|p| p.name.as_str()).collect();
+    let mut seen_names: BTreeMap<&str, usize> = BTreeMap::new();
+    for (i, name) in names.iter().enumerate() {
+        let field = format!("names[{}]", i);
+        if let Some(first) = seen_names.insert(name, i) {
+            issues.push(DataIssue::new(
+                metadata_file,
+                Some(&field),
+                format!("The name {} is already at names[{}]", name, first),
+            ));
+        }
+        if !pokemon_names.contains(name.as_str()) {
+            issues.push(DataIssue::new(
+                metadata_file,
+                Some(&field),
+                format!("There is no pokemon file for {}", name),
+            ));
+        }
+    }
+    for pokemon in pokemons.values() {
+        if !seen_names.contains_key(pokemon.name.as_str()) {
+            issues.push(DataIssue::new(
+                metadata_file,
+                Some("names"),
+                format!("The pokemon {} ({}) is missing", pokemon.name, pokemon.id),
+            ));
+        }
+    }
+}
+
+// Every language must have the same pokemons, otherwise the daily pokemon could be missing in one
+fn validate_same_pokemons(
+    data_dir: &Path,
+    pokemons_by_lang: &BTreeMap<Lang, BTreeMap<PokemonId, Pokemon>>,
+    issues: &mut Vec<DataIssue>,
+) {
+    let all_ids: BTreeSet<PokemonId> = pokemons_by_lang
+        .values()
+        .flat_map(|pokemons| pokemons.keys().copied())
+        .collect();
+    for (lang, pokemons) in pokemons_by_lang {
+        let pokedle_dir = data_dir.join(lang).join("pokedle");
+        if pokemons.len() != all_ids.len() {
+            issues.push(DataIssue::new(
+                &pokedle_dir,
+                None,
+                format!(
+                    "There are {} pokemons instead of {}",
+                    pokemons.len(),
+                    all_ids.len()
+                ),
+            ));
+        }
+        for id in all_ids.iter().filter(|id| !pokemons.contains_key(id)) {
+            issues.push(DataIssue::new(
+                &pokedle_dir.join(format!("{}.json", id)),
+                None,
+                format!("The pokemon {} is missing in {}", id, lang),
+            ));
+        }
+    }
+}
+
+/*
+    Tests
+*/
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BaseStats;
+    use std::fs;
+
+    fn write_file(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn pokemon_json(name: &str, height: f64, types: &str, generation: u8) -> String {
+        format!(
+            r#"{{"name": "{}", "height": {}, "weight": 6.9, "types": {}, "color": "Vert", "generation": {}}}"#,
+            name, height, types, generation
+        )
+    }
+
+    fn get_fields(issues: &[DataIssue], file: &str) -> Vec<Option<String>> {
+        issues
+            .iter()
+            .filter(|issue| issue.path.ends_with(file))
+            .map(|issue| issue.field.clone())
+            .collect()
+    }
+
+    #[test]
+    fn validate_valid_data() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("small_test_data");
+        assert_eq!(
+            validate_data(d.clone(), vec![String::from("fr"), String::from("de")]),
+            vec![]
+        );
+
+        let issues = validate_data(d, vec![String::from("en")]);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].path.ends_with("generated_data/en"));
+        assert_eq!(issues[0].message, "The language en is missing");
+    }
+
+    #[test]
+    fn validate_invalid_data() {
+        let mut d = std::env::temp_dir();
+        d.push("pokedle_validate_invalid_data");
+        let _ = fs::remove_dir_all(&d);
+        let data_dir = d.join("generated_data");
+
+        write_file(
+            &data_dir.join("fr/metadata.json"),
+            r#"{"names": ["Bulbizarre", "Bulbizarre", "Mewtwo"]}"#,
+        );
+        write_file(
+            &data_dir.join("fr/pokedle/1.json"),
+            &pokemon_json("Bulbizarre", 0.7, r#"["Plante", "Poison"]"#, 1),
+        );
+        write_file(
+            &data_dir.join("fr/pokedle/2.json"),
+            &pokemon_json("Herbizarre", -1.0, r#"["Plante", "Poison"]"#, 1),
+        );
+        write_file(
+            &data_dir.join("fr/pokedle/3.json"),
+            &pokemon_json("Bulbizarre", 2.0, r#"["Plnate"]"#, 12),
+        );
+        write_file(
+            &data_dir.join("de/pokedle/1.json"),
+            &pokemon_json("Bisasam", 0.7, r#"["Pflanze", "Gift"]"#, 2),
+        );
+
+        let issues = validate_data(d.clone(), vec![String::from("fr")]);
+
+        // The file is named, and the field when there is one
+        assert_eq!(
+            get_fields(&issues, "fr/metadata.json"),
+            vec![
+                Some(String::from("names[1]")),
+                Some(String::from("names[2]"))
+            ]
+        );
+        assert_eq!(
+            get_fields(&issues, "fr/pokedle/2.json"),
+            vec![Some(String::from("height"))]
+        );
+        assert_eq!(
+            get_fields(&issues, "fr/pokedle/3.json"),
+            vec![
+                Some(String::from("types[0]")),
+                Some(String::from("generation")),
+                Some(String::from("name"))
+            ]
+        );
+        assert_eq!(get_fields(&issues, "de/metadata.json"), vec![None]);
+        assert_eq!(
+            get_fields(&issues, "de/pokedle/1.json"),
+            vec![Some(String::from("generation"))]
+        );
+        assert_eq!(get_fields(&issues, "de/pokedle"), vec![None]);
+        assert_eq!(get_fields(&issues, "de/pokedle/3.json"), vec![None]);
+        assert_eq!(issues.len(), 10);
+
+        fs::remove_dir_all(d).unwrap();
+    }
+
+    #[test]
+    fn validate_the_base_stats() {
+        let mut pokemon: Pokemon = serde_json::from_str(&pokemon_json(
+            "Bulbizarre",
+            0.7,
+            r#"["Plante", "Poison"]"#,
+            1,
+        ))
+        .unwrap();
+        pokemon.base_stats = Some(BaseStats {
+            hp: 45,
+            attack: 0,
+            defense: 49,
+            special_attack: 65,
+            special_defense: 65,
+            speed: 256,
+        });
+
+        let mut issues = vec![];
+        validate_pokemon(Path::new("1.json"), &pokemon, &mut issues);
+        assert_eq!(
+            get_fields(&issues, "1.json"),
+            vec![
+                Some(String::from("base_stats.attack")),
+                Some(String::from("base_stats.speed"))
+            ]
+        );
+        assert_eq!(
+            issues[1].message,
+            "A base stat must be between 1 and 255, not 256"
+        );
+    }
+}
@@ -0,0 +1,10 @@
+# Checks the data before deploying it, usage: python3 validate_data.py [pokle_dir]
+import sys
+
+import pokedle
+
+pokle_dir = sys.argv[1] if len(sys.argv) > 1 else "poke_data"
+issues = pokedle.validate_data(pokle_dir, ["fr", "de"])
+for issue in issues:
+	print(issue.to_string())
+sys.exit(1 if issues else 0)